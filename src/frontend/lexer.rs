@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use crate::general::{
+    bigint::BigInt,
     error::{Error, ErrorType},
     object::Object,
     token::{Token, TokenType},
@@ -8,7 +9,6 @@ use crate::general::{
 
 pub struct Lexer {
     source: String,
-    source_as_u8: Vec<u8>,
     source_len: usize,
     keywords: HashMap<String, TokenType>,
     line: usize,
@@ -19,7 +19,6 @@ impl Lexer {
     pub fn new(source: &str) -> Self {
         Self {
             source: source.to_string(),
-            source_as_u8: source.as_bytes().to_vec(),
             source_len: source.len(),
             keywords: HashMap::new(),
             line: 1,
@@ -31,313 +30,902 @@ impl Lexer {
         let mut tokens = Vec::new();
         self.init_keywords();
         while !self.is_eof() {
-            let start = self.current;
-            match self.peek() {
-                ' ' | '\t' | '\r' => {
-                    self.advance();
-                }
-                '\n' => {
+            self.lex_token(&mut tokens)?;
+        }
+        tokens.push(Token::new(TokenType::EOF, "\0", Object::Nil, self.line));
+        Ok(tokens)
+    }
+
+    fn lex_token(&mut self, tokens: &mut Vec<Token>) -> Result<(), Error> {
+        let start = self.current;
+        match self.peek() {
+            ' ' | '\t' | '\r' => {
+                self.advance();
+            }
+            '\n' => {
+                self.advance();
+                self.line += 1
+            }
+            '(' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::OpenParen,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            ')' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::CloseParen,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '{' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::OpenCurly,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '}' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::CloseCurly,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '[' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::OpenBracket,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            ']' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::CloseBracket,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '+' => {
+                self.advance();
+                tokens.push(Token::new(
+                    self.compound(TokenType::Plus, TokenType::PlusEqual),
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '-' => {
+                self.advance();
+                tokens.push(Token::new(
+                    self.compound(TokenType::Minus, TokenType::MinusEqual),
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '*' if self.peek_at(1) == Some('*') => {
+                self.advance();
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::Power,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '*' => {
+                self.advance();
+                tokens.push(Token::new(
+                    self.compound(TokenType::Multiplication, TokenType::MultiplicationEqual),
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '%' => {
+                self.advance();
+                tokens.push(Token::new(
+                    self.compound(TokenType::Modulo, TokenType::ModuloEqual),
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            ',' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::Comma,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '.' if self.peek_at(1) == Some('.') => {
+                self.advance();
+                self.advance();
+                if !self.is_eof() && self.peek() == '=' {
                     self.advance();
-                    self.line += 1
+                    tokens.push(Token::new(
+                        TokenType::DotDotEqual,
+                        &self.source[start..self.current],
+                        Object::Nil,
+                        self.line,
+                    ));
+                } else {
+                    tokens.push(Token::new(
+                        TokenType::DotDot,
+                        &self.source[start..self.current],
+                        Object::Nil,
+                        self.line,
+                    ));
                 }
-                '(' => {
+            }
+            '.' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::Dot,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            ':' => {
+                self.advance();
+                if !self.is_eof() && self.peek() == ':' {
                     self.advance();
                     tokens.push(Token::new(
-                        TokenType::OpenParen,
+                        TokenType::ColonColon,
+                        &self.source[start..self.current],
+                        Object::Nil,
+                        self.line,
+                    ));
+                } else {
+                    tokens.push(Token::new(
+                        TokenType::Colon,
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
                 }
-                ')' => {
+            }
+            ';' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::Semicolon,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '"' => {
+                let line = self.line;
+                self.advance();
+                let string = self.extract_string('"')?;
+                tokens.push(Token::new(
+                    TokenType::String,
+                    &self.source[start..self.current],
+                    Object::String(string),
+                    line,
+                ));
+            }
+            '\'' if self.is_label(tokens.last()) => {
+                self.advance();
+                let label = self.extract_identifier()?;
+                tokens.push(Token::new(
+                    TokenType::Label,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    label.line,
+                ));
+            }
+            '\'' => {
+                let line = self.line;
+                self.advance();
+                let string = self.extract_string('\'')?;
+                tokens.push(Token::new(
+                    TokenType::String,
+                    &self.source[start..self.current],
+                    Object::String(string),
+                    line,
+                ));
+            }
+            '/' => {
+                self.advance();
+                if self.peek() == '/' {
                     self.advance();
+                    self.ignore_comment();
+                } else {
                     tokens.push(Token::new(
-                        TokenType::CloseParen,
+                        self.compound(TokenType::Division, TokenType::DivisionEqual),
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
                 }
-                '{' => {
+            }
+            '=' => {
+                self.advance();
+                if self.peek() == '=' {
                     self.advance();
                     tokens.push(Token::new(
-                        TokenType::OpenCurly,
+                        TokenType::EqualEqual,
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
-                }
-                '}' => {
+                } else if self.peek() == '>' {
                     self.advance();
                     tokens.push(Token::new(
-                        TokenType::CloseCurly,
+                        TokenType::FatArrow,
+                        &self.source[start..self.current],
+                        Object::Nil,
+                        self.line,
+                    ));
+                } else {
+                    tokens.push(Token::new(
+                        TokenType::Equal,
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
                 }
-                '+' => {
+            }
+            '!' => {
+                self.advance();
+                if self.peek() == '=' {
                     self.advance();
                     tokens.push(Token::new(
-                        TokenType::Plus,
+                        TokenType::BangEqual,
+                        &self.source[start..self.current],
+                        Object::Nil,
+                        self.line,
+                    ));
+                } else {
+                    tokens.push(Token::new(
+                        TokenType::Bang,
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
                 }
-                '-' => {
+            }
+            '>' => {
+                self.advance();
+                if self.peek() == '>' {
                     self.advance();
                     tokens.push(Token::new(
-                        TokenType::Minus,
+                        self.compound(TokenType::ShiftRight, TokenType::ShiftRightEqual),
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
-                }
-                '*' => {
+                } else if self.peek() == '=' {
                     self.advance();
                     tokens.push(Token::new(
-                        TokenType::Multiplication,
+                        TokenType::GreaterEqual,
+                        &self.source[start..self.current],
+                        Object::Nil,
+                        self.line,
+                    ));
+                } else {
+                    tokens.push(Token::new(
+                        TokenType::Greater,
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
                 }
-                '%' => {
+            }
+            '<' => {
+                self.advance();
+                if self.peek() == '<' {
                     self.advance();
                     tokens.push(Token::new(
-                        TokenType::Modulo,
+                        self.compound(TokenType::ShiftLeft, TokenType::ShiftLeftEqual),
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
-                }
-                ',' => {
+                } else if self.peek() == '=' {
                     self.advance();
                     tokens.push(Token::new(
-                        TokenType::Comma,
+                        TokenType::LessEqual,
+                        &self.source[start..self.current],
+                        Object::Nil,
+                        self.line,
+                    ));
+                } else {
+                    tokens.push(Token::new(
+                        TokenType::Less,
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
                 }
-                ';' => {
+            }
+            '^' => {
+                self.advance();
+                tokens.push(Token::new(
+                    self.compound(TokenType::BitwiseXor, TokenType::BitwiseXorEqual),
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            // '//' already starts a comment, so floor division is written '~/'.
+            '~' if self.peek_at(1) == Some('/') => {
+                self.advance();
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::FloorDivision,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '~' => {
+                self.advance();
+                tokens.push(Token::new(
+                    TokenType::BitwiseNot,
+                    &self.source[start..self.current],
+                    Object::Nil,
+                    self.line,
+                ));
+            }
+            '&' => {
+                self.advance();
+                if self.peek() == '&' {
                     self.advance();
                     tokens.push(Token::new(
-                        TokenType::Semicolon,
+                        TokenType::And,
+                        &self.source[start..self.current],
+                        Object::Nil,
+                        self.line,
+                    ));
+                } else {
+                    tokens.push(Token::new(
+                        self.compound(TokenType::BitwiseAnd, TokenType::BitwiseAndEqual),
                         &self.source[start..self.current],
                         Object::Nil,
                         self.line,
                     ));
                 }
-                '"' => {
+            }
+            '|' => {
+                self.advance();
+                if self.peek() == '|' {
                     self.advance();
-                    let string = self.extract_string('"')?;
                     tokens.push(Token::new(
-                        TokenType::String,
+                        TokenType::Or,
                         &self.source[start..self.current],
-                        Object::String(string),
+                        Object::Nil,
+                        self.line,
+                    ));
+                } else {
+                    tokens.push(Token::new(
+                        self.compound(TokenType::BitwiseOr, TokenType::BitwiseOrEqual),
+                        &self.source[start..self.current],
+                        Object::Nil,
                         self.line,
                     ));
                 }
-                '\'' => {
-                    self.advance();
-                    let string = self.extract_string('\'')?;
+            }
+            peek => {
+                if peek.is_ascii_digit() {
+                    let number = self.extract_number(tokens.last())?;
+                    tokens.push(number);
+                } else if peek == 'f' && matches!(self.peek_at(1), Some('"' | '\'')) {
+                    self.extract_format_string(tokens)?;
+                } else if peek == 'r' && self.is_raw_string() {
+                    let line = self.line;
+                    let string = self.extract_raw_string()?;
                     tokens.push(Token::new(
                         TokenType::String,
                         &self.source[start..self.current],
                         Object::String(string),
-                        self.line,
+                        line,
                     ));
-                }
-                '/' => {
-                    self.advance();
-                    if self.peek() == '/' {
-                        self.advance();
-                        self.ignore_comment();
-                    } else {
-                        tokens.push(Token::new(
-                            TokenType::Division,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    }
-                }
-                '=' => {
-                    self.advance();
-                    if self.peek() == '=' {
-                        self.advance();
-                        tokens.push(Token::new(
-                            TokenType::EqualEqual,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    } else {
-                        tokens.push(Token::new(
-                            TokenType::Equal,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    }
-                }
-                '!' => {
-                    self.advance();
-                    if self.peek() == '=' {
-                        self.advance();
-                        tokens.push(Token::new(
-                            TokenType::BangEqual,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    } else {
-                        tokens.push(Token::new(
-                            TokenType::Bang,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    }
-                }
-                '>' => {
-                    self.advance();
-                    if self.peek() == '=' {
-                        self.advance();
-                        tokens.push(Token::new(
-                            TokenType::GreaterEqual,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    } else {
-                        tokens.push(Token::new(
-                            TokenType::Greater,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    }
-                }
-                '<' => {
-                    self.advance();
-                    if self.peek() == '=' {
-                        self.advance();
-                        tokens.push(Token::new(
-                            TokenType::LessEqual,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    } else {
-                        tokens.push(Token::new(
-                            TokenType::Less,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    }
-                }
-                '&' => {
-                    self.advance();
-                    if self.peek() == '&' {
-                        self.advance();
-                        tokens.push(Token::new(
-                            TokenType::And,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    } else {
-                        tokens.push(Token::new(
-                            TokenType::BitwiseAnd,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    }
-                }
-                '|' => {
-                    self.advance();
-                    if self.peek() == '|' {
-                        self.advance();
-                        tokens.push(Token::new(
-                            TokenType::Or,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    } else {
-                        tokens.push(Token::new(
-                            TokenType::BitwiseOr,
-                            &self.source[start..self.current],
-                            Object::Nil,
-                            self.line,
-                        ));
-                    }
-                }
-                peek => {
-                    if peek.is_digit(10) {
-                        tokens.push(self.extract_number()?);
-                    } else if peek.is_alphabetic() || peek == '_' {
-                        tokens.push(self.extract_identifier()?);
-                    } else {
-                        return Err(self.error(&format!("Invalid charecter '{}'", peek)));
-                    }
+                } else if peek.is_alphabetic() || peek == '_' {
+                    tokens.push(self.extract_identifier()?);
+                } else {
+                    return Err(self.error(&format!("Invalid charecter '{}'", peek)));
                 }
             }
         }
-        tokens.push(Token::new(TokenType::EOF, "\0", Object::Nil, self.line));
-        Ok(tokens)
+        Ok(())
+    }
+
+    // Gives the compound assignment form of the operator just lexed when an
+    // '=' follows, as in `+=` or `<<=`.
+    fn compound(&mut self, operator: TokenType, compound: TokenType) -> TokenType {
+        if self.peek() == '=' {
+            self.advance();
+            compound
+        } else {
+            operator
+        }
     }
 
     fn advance(&mut self) {
-        self.current += 1;
+        self.current += self.peek().len_utf8();
     }
 
+    // Gives '\0' at the end of the source so callers can look ahead freely.
     fn peek(&self) -> char {
-        self.source_as_u8[self.current] as char
+        self.peek_at(0).unwrap_or('\0')
     }
 
     fn is_eof(&self) -> bool {
-        if self.source_len > self.current {
-            false
-        } else {
-            true
+        self.source_len <= self.current
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.source.get(self.current..)?.chars().nth(offset)
+    }
+
+    // A quote starts a loop label (`'outer`) rather than a string when it
+    // follows 'break'/'continue', or when it is written as `'outer:` right
+    // before 'while', 'for' or 'loop' where a statement can start.
+    fn is_label(&self, previous: Option<&Token>) -> bool {
+        let mut offset = self.word_end(1);
+        if offset == 1 {
+            return false;
+        }
+        let previous = previous.map(|token| &token.ttype);
+        match self.peek_at(offset) {
+            Some('\'') => false,
+            Some(':') => {
+                if !matches!(
+                    previous,
+                    None | Some(TokenType::Semicolon)
+                        | Some(TokenType::OpenCurly)
+                        | Some(TokenType::CloseCurly)
+                ) {
+                    return false;
+                }
+                offset += 1;
+                while self.peek_at(offset).is_some_and(char::is_whitespace) {
+                    offset += 1;
+                }
+                let end = self.word_end(offset);
+                let word: String = (offset..end).filter_map(|i| self.peek_at(i)).collect();
+                matches!(word.as_str(), "while" | "for" | "loop")
+            }
+            _ => matches!(previous, Some(TokenType::Break) | Some(TokenType::Continue)),
         }
     }
 
-    fn extract_number(&mut self) -> Result<Token, Error> {
-        let mut number_str = String::new();
-        while self.peek().is_digit(10) && !self.is_eof() {
-            number_str.push(self.peek());
+    // Offset just past the identifier starting at `offset`, or `offset`
+    // itself when no identifier starts there.
+    fn word_end(&self, offset: usize) -> usize {
+        match self.peek_at(offset) {
+            Some(first) if first.is_alphabetic() || first == '_' => {}
+            _ => return offset,
+        }
+        let mut end = offset + 1;
+        while self
+            .peek_at(end)
+            .is_some_and(|next| next.is_alphanumeric() || next == '_')
+        {
+            end += 1;
+        }
+        end
+    }
+
+    fn extract_number(&mut self, previous: Option<&Token>) -> Result<Token, Error> {
+        let start = self.current;
+        if self.peek() == '0' {
+            let radix = match self.peek_at(1) {
+                Some('x') => Some((16, "hexadecimal")),
+                Some('o') => Some((8, "octal")),
+                Some('b') => Some((2, "binary")),
+                _ => None,
+            };
+            if let Some((radix, name)) = radix {
+                self.current += 2;
+                return self.extract_radix_number(start, radix, name);
+            }
+        }
+        self.skip_digits();
+        // Right after a '.' the number is a tuple index, so that `pair.0.1`
+        // does not read `0.1` as a float.
+        let tuple_index = previous.is_some_and(|token| token.ttype == TokenType::Dot);
+        let mut float = false;
+        if !tuple_index
+            && self.peek_at(0) == Some('.')
+            && self.peek_at(1).is_some_and(|next| next.is_ascii_digit())
+        {
             self.advance();
+            self.skip_digits();
+            float = true;
         }
-        if let Ok(number) = number_str.parse() {
-            Ok(Token::new(
-                TokenType::Number,
-                &number_str,
-                Object::Number(number),
-                self.line,
-            ))
+        if !tuple_index && matches!(self.peek_at(0), Some('e' | 'E')) {
+            self.advance();
+            if matches!(self.peek_at(0), Some('+' | '-')) {
+                self.advance();
+            }
+            if !self.peek_at(0).is_some_and(|next| next.is_ascii_digit()) {
+                return Err(self.error(&format!(
+                    "Expected digits in the exponent of '{}'",
+                    &self.source[start..self.current]
+                )));
+            }
+            self.skip_digits();
+            float = true;
+        }
+        self.check_suffix(start)?;
+        let lexeme = &self.source[start..self.current];
+        let digits: String = lexeme.chars().filter(|c| *c != '_').collect();
+        let literal = if float {
+            match digits.parse::<f64>() {
+                Ok(number) if number.is_finite() => Object::Float(number),
+                _ => return Err(self.error(&format!("Float literal '{}' is out of range", lexeme))),
+            }
         } else {
-            Err(self.error("Expected a digit"))
+            match digits.parse() {
+                Ok(number) => Object::Integer(number),
+                Err(_) => Object::integer(BigInt::from_str_radix(&digits, 10).unwrap()),
+            }
+        };
+        Ok(Token::new(TokenType::Number, lexeme, literal, self.line))
+    }
+
+    fn extract_radix_number(
+        &mut self,
+        start: usize,
+        radix: u32,
+        name: &str,
+    ) -> Result<Token, Error> {
+        while self
+            .peek_at(0)
+            .is_some_and(|next| next.is_ascii_alphanumeric() || next == '_')
+        {
+            self.advance();
+        }
+        let lexeme = &self.source[start..self.current];
+        let digits: String = lexeme[2..].chars().filter(|c| *c != '_').collect();
+        if digits.is_empty() {
+            return Err(self.error(&format!(
+                "Expected {} digits after '{}'",
+                name,
+                &lexeme[..2]
+            )));
+        }
+        if let Some(invalid) = digits.chars().find(|digit| !digit.is_digit(radix)) {
+            return Err(self.error(&format!(
+                "Invalid digit '{}' in {} literal '{}'",
+                invalid, name, lexeme
+            )));
         }
+        let literal = match i64::from_str_radix(&digits, radix) {
+            Ok(number) => Object::Integer(number),
+            Err(_) => Object::integer(BigInt::from_str_radix(&digits, radix).unwrap()),
+        };
+        Ok(Token::new(TokenType::Number, lexeme, literal, self.line))
     }
 
+    // Digits may be grouped with underscores, as in `1_000_000`.
+    fn skip_digits(&mut self) {
+        while self
+            .peek_at(0)
+            .is_some_and(|next| next.is_ascii_digit() || next == '_')
+        {
+            self.advance();
+        }
+    }
+
+    fn check_suffix(&mut self, start: usize) -> Result<(), Error> {
+        let suffix = self.current;
+        while self
+            .peek_at(0)
+            .is_some_and(|next| next.is_ascii_alphanumeric() || next == '_')
+        {
+            self.advance();
+        }
+        if suffix == self.current {
+            return Ok(());
+        }
+        Err(self.error(&format!(
+            "Invalid suffix '{}' on number literal '{}'",
+            &self.source[suffix..self.current],
+            &self.source[start..self.current]
+        )))
+    }
+
+    // Strings may span lines. An unterminated one is reported at the line
+    // it starts on, since that is where the missing quote belongs.
     fn extract_string(&mut self, terminator: char) -> Result<String, Error> {
+        let line = self.line;
         let mut string = String::new();
-        while self.peek().is_ascii() && !self.is_eof() && self.peek() != terminator {
-            string.push(self.peek());
+        loop {
+            if self.is_eof() {
+                return Err(Error::new(
+                    ErrorType::LexingError,
+                    "Unterminated string",
+                    line,
+                ));
+            }
+            let character = self.peek();
             self.advance();
+            match character {
+                character if character == terminator => return Ok(string),
+                '\\' if self.is_eof() => {}
+                '\\' => string.extend(self.extract_escape()?),
+                '\n' => {
+                    self.line += 1;
+                    string.push('\n');
+                }
+                character => string.push(character),
+            }
         }
-        if self.is_eof() {
-            Err(self.error("Unterminated string"))
-        } else {
+    }
+
+    // Called just after the backslash. A backslash at the end of a line
+    // continues the string on the next one, skipping the indentation.
+    fn extract_escape(&mut self) -> Result<Option<char>, Error> {
+        let escape = self.peek();
+        self.advance();
+        let character = match escape {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' | '"' | '\'' => escape,
+            'x' => return self.extract_ascii_escape().map(Some),
+            'u' => return self.extract_unicode_escape().map(Some),
+            '\n' => {
+                self.line += 1;
+                while !self.is_eof() && self.peek().is_whitespace() {
+                    if self.peek() == '\n' {
+                        self.line += 1;
+                    }
+                    self.advance();
+                }
+                return Ok(None);
+            }
+            escape => {
+                return Err(self.error(&format!("Unknown escape sequence '\\{}'", escape)));
+            }
+        };
+        Ok(Some(character))
+    }
+
+    // `\x41`: exactly two hex digits, limited to ASCII like in Rust.
+    fn extract_ascii_escape(&mut self) -> Result<char, Error> {
+        let start = self.current;
+        for _ in 0..2 {
+            if !self.peek().is_ascii_hexdigit() {
+                return Err(self.error("Expected two hexadecimal digits after '\\x'"));
+            }
+            self.advance();
+        }
+        let digits = &self.source[start..self.current];
+        match u8::from_str_radix(digits, 16) {
+            Ok(byte) if byte.is_ascii() => Ok(byte as char),
+            _ => Err(self.error(&format!(
+                "Escape '\\x{}' is out of range, use '\\u{{..}}' for non-ASCII characters",
+                digits
+            ))),
+        }
+    }
+
+    // `\u{1F600}`: one to six hex digits naming a Unicode scalar value.
+    fn extract_unicode_escape(&mut self) -> Result<char, Error> {
+        if self.peek() != '{' {
+            return Err(self.error("Expected '{' after '\\u'"));
+        }
+        self.advance();
+        let start = self.current;
+        while self.peek().is_ascii_hexdigit() {
+            self.advance();
+        }
+        let digits = self.source[start..self.current].to_string();
+        if self.peek() != '}' {
+            return Err(self.error("Unterminated unicode escape, expected '}'"));
+        }
+        self.advance();
+        if digits.is_empty() || digits.len() > 6 {
+            return Err(self.error(&format!("Invalid unicode escape '\\u{{{}}}'", digits)));
+        }
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| {
+                self.error(&format!(
+                    "Invalid unicode escape '\\u{{{}}}', not a valid character",
+                    digits
+                ))
+            })
+    }
+
+    // `f"{name:>8} is {age + 1}"` becomes a FormatStart token, the literal
+    // parts as strings, every interpolation as its expression's tokens
+    // between InterpolationStart and InterpolationEnd, and a FormatEnd.
+    // Lexing the expressions in place keeps their lines right for errors.
+    fn extract_format_string(&mut self, tokens: &mut Vec<Token>) -> Result<(), Error> {
+        let line = self.line;
+        let start = self.current;
+        self.advance();
+        let terminator = self.peek();
+        self.advance();
+        tokens.push(Token::new(
+            TokenType::FormatStart,
+            &self.source[start..self.current],
+            Object::Nil,
+            line,
+        ));
+        let mut literal = String::new();
+        loop {
+            if self.is_eof() {
+                return Err(Error::new(
+                    ErrorType::LexingError,
+                    "Unterminated string",
+                    line,
+                ));
+            }
+            let character = self.peek();
+            self.advance();
+            match character {
+                character if character == terminator => break,
+                '{' | '}' if self.peek() == character => {
+                    self.advance();
+                    literal.push(character);
+                }
+                '{' => {
+                    self.push_format_literal(tokens, &mut literal);
+                    self.extract_interpolation(tokens)?;
+                }
+                '}' => {
+                    return Err(self
+                        .error("Unmatched '}' in format string, write '}}' for a literal brace"));
+                }
+                '\\' if self.is_eof() => {}
+                '\\' => literal.extend(self.extract_escape()?),
+                '\n' => {
+                    self.line += 1;
+                    literal.push('\n');
+                }
+                character => literal.push(character),
+            }
+        }
+        self.push_format_literal(tokens, &mut literal);
+        tokens.push(Token::new(
+            TokenType::FormatEnd,
+            &terminator.to_string(),
+            Object::Nil,
+            self.line,
+        ));
+        Ok(())
+    }
+
+    fn push_format_literal(&self, tokens: &mut Vec<Token>, literal: &mut String) {
+        if !literal.is_empty() {
+            let literal = std::mem::take(literal);
+            tokens.push(Token::new(
+                TokenType::String,
+                &literal,
+                Object::String(literal.clone()),
+                self.line,
+            ));
+        }
+    }
+
+    // Called just after the opening brace. The expression ends at the first
+    // `}` or `:` that is not nested in brackets, and what follows a `:` up
+    // to the brace is the format spec, kept as the literal of the closing
+    // InterpolationEnd token.
+    fn extract_interpolation(&mut self, tokens: &mut Vec<Token>) -> Result<(), Error> {
+        let line = self.line;
+        tokens.push(Token::new(
+            TokenType::InterpolationStart,
+            "{",
+            Object::Nil,
+            line,
+        ));
+        let mut depth = 0_usize;
+        loop {
+            if self.is_eof() {
+                return Err(Error::new(
+                    ErrorType::LexingError,
+                    "Unterminated interpolation in format string, expected '}'",
+                    line,
+                ));
+            }
+            match self.peek() {
+                '}' if depth == 0 => break,
+                ':' if depth == 0 && self.peek_at(1) != Some(':') => break,
+                _ => {}
+            }
+            let lexed = tokens.len();
+            self.lex_token(tokens)?;
+            for token in &tokens[lexed..] {
+                match token.ttype {
+                    TokenType::OpenParen | TokenType::OpenCurly | TokenType::OpenBracket => {
+                        depth += 1
+                    }
+                    TokenType::CloseParen | TokenType::CloseCurly | TokenType::CloseBracket => {
+                        depth = depth.saturating_sub(1)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let start = self.current;
+        if self.peek() == ':' {
+            self.advance();
+            while !self.is_eof() && !matches!(self.peek(), '}' | '\n') {
+                self.advance();
+            }
+            if self.peek() != '}' {
+                return Err(self.error("Unterminated format spec, expected '}'"));
+            }
+        }
+        let spec = self.source[start..self.current]
+            .strip_prefix(':')
+            .unwrap_or_default()
+            .to_string();
+        self.advance();
+        tokens.push(Token::new(
+            TokenType::InterpolationEnd,
+            &self.source[start..self.current],
+            Object::String(spec),
+            self.line,
+        ));
+        Ok(())
+    }
+
+    // `r"..."` or `r#"..."#`, with as many hashes as needed to allow quotes
+    // inside. Nothing is escaped in a raw string.
+    fn is_raw_string(&self) -> bool {
+        let mut offset = 1;
+        while self.peek_at(offset) == Some('#') {
+            offset += 1;
+        }
+        self.peek_at(offset) == Some('"')
+    }
+
+    fn extract_raw_string(&mut self) -> Result<String, Error> {
+        let line = self.line;
+        self.advance();
+        let mut hashes = 0;
+        while self.peek() == '#' {
+            hashes += 1;
+            self.advance();
+        }
+        self.advance();
+        let start = self.current;
+        loop {
+            if self.is_eof() {
+                return Err(Error::new(
+                    ErrorType::LexingError,
+                    "Unterminated raw string",
+                    line,
+                ));
+            }
+            if self.peek() == '"' && (1..=hashes).all(|offset| self.peek_at(offset) == Some('#')) {
+                let string = self.source[start..self.current].to_string();
+                self.current += 1 + hashes;
+                return Ok(string);
+            }
+            if self.peek() == '\n' {
+                self.line += 1;
+            }
             self.advance();
-            Ok(string)
         }
     }
 
@@ -391,12 +979,220 @@ impl Lexer {
 
     fn init_keywords(&mut self) {
         self.keywords.insert("let".to_string(), TokenType::Let);
+        self.keywords.insert("mut".to_string(), TokenType::Mut);
+        self.keywords.insert("const".to_string(), TokenType::Const);
         self.keywords.insert("if".to_string(), TokenType::If);
         self.keywords.insert("else".to_string(), TokenType::Else);
         self.keywords.insert("while".to_string(), TokenType::While);
+        self.keywords.insert("for".to_string(), TokenType::For);
+        self.keywords.insert("in".to_string(), TokenType::In);
+        self.keywords.insert("not".to_string(), TokenType::Not);
+        self.keywords.insert("fn".to_string(), TokenType::Fn);
+        self.keywords
+            .insert("struct".to_string(), TokenType::Struct);
+        self.keywords.insert("enum".to_string(), TokenType::Enum);
+        self.keywords.insert("impl".to_string(), TokenType::Impl);
+        self.keywords.insert("match".to_string(), TokenType::Match);
+        self.keywords.insert("loop".to_string(), TokenType::Loop);
+        self.keywords.insert("try".to_string(), TokenType::Try);
+        self.keywords.insert("catch".to_string(), TokenType::Catch);
+        self.keywords
+            .insert("return".to_string(), TokenType::Return);
+        self.keywords.insert("break".to_string(), TokenType::Break);
+        self.keywords
+            .insert("continue".to_string(), TokenType::Continue);
+        self.keywords.insert("pub".to_string(), TokenType::Pub);
+        self.keywords.insert("mod".to_string(), TokenType::Mod);
+        self.keywords.insert("use".to_string(), TokenType::Use);
         self.keywords.insert("true".to_string(), TokenType::True);
         self.keywords.insert("false".to_string(), TokenType::False);
         self.keywords.insert("nil".to_string(), TokenType::Nil);
         self.keywords.insert("print".to_string(), TokenType::Print);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types(source: &str) -> Vec<TokenType> {
+        let tokens = Lexer::new(source).lex().unwrap();
+        tokens.into_iter().map(|token| token.ttype).collect()
+    }
+
+    fn literal(source: &str) -> Object {
+        Lexer::new(source).lex().unwrap().remove(0).literal
+    }
+
+    fn error(source: &str) -> (String, usize) {
+        let error = Lexer::new(source).lex().unwrap_err();
+        (error.message().to_string(), error.line())
+    }
+
+    #[test]
+    fn lexes_number_literals() {
+        assert_eq!(literal("3.25"), Object::Float(3.25));
+        assert_eq!(literal("2.5e-3"), Object::Float(2.5e-3));
+        assert_eq!(literal("1E3"), Object::Float(1000.));
+        assert!(matches!(literal("1e3"), Object::Float(_)));
+        assert!(matches!(literal("42"), Object::Integer(42)));
+        assert_eq!(literal("0xff"), Object::Integer(255));
+        assert_eq!(literal("0xFF_FF"), Object::Integer(65535));
+        assert_eq!(literal("0o17"), Object::Integer(15));
+        assert_eq!(literal("0b1010"), Object::Integer(10));
+        assert_eq!(literal("1_000_000"), Object::Integer(1_000_000));
+        assert_eq!(
+            literal("0xffffffffffffffff").to_string(),
+            "18446744073709551615"
+        );
+        assert_eq!(
+            literal("99999999999999999999").to_string(),
+            "99999999999999999999"
+        );
+    }
+
+    #[test]
+    fn lexes_dotted_numbers_after_a_dot_as_tuple_indices() {
+        assert_eq!(
+            types("t.0.1"),
+            [
+                TokenType::Identifier,
+                TokenType::Dot,
+                TokenType::Number,
+                TokenType::Dot,
+                TokenType::Number,
+                TokenType::EOF
+            ]
+        );
+        assert_eq!(types("1..3")[..2], [TokenType::Number, TokenType::DotDot]);
+    }
+
+    #[test]
+    fn rejects_malformed_number_literals() {
+        let message = |source| error(source).0;
+        assert_eq!(message("0x"), "Expected hexadecimal digits after '0x'");
+        assert_eq!(message("0b_"), "Expected binary digits after '0b'");
+        assert_eq!(
+            message("0b102"),
+            "Invalid digit '2' in binary literal '0b102'"
+        );
+        assert_eq!(message("0o8"), "Invalid digit '8' in octal literal '0o8'");
+        assert_eq!(
+            message("0xfg"),
+            "Invalid digit 'g' in hexadecimal literal '0xfg'"
+        );
+        assert_eq!(message("1e"), "Expected digits in the exponent of '1e'");
+        assert_eq!(
+            message("1.5e+"),
+            "Expected digits in the exponent of '1.5e+'"
+        );
+        assert_eq!(
+            message("12abc"),
+            "Invalid suffix 'abc' on number literal '12abc'"
+        );
+        assert_eq!(message("1e999"), "Float literal '1e999' is out of range");
+        assert_eq!(error("\n\n0x").1, 3);
+    }
+
+    #[test]
+    fn lexes_string_escapes() {
+        let string = |source| literal(source).to_string();
+        assert_eq!(string(r#""a\tb\nc\r\0""#), "a\tb\nc\r\0");
+        assert_eq!(string(r#""\"\'\\""#), "\"'\\");
+        assert_eq!(string(r#"'it\'s'"#), "it's");
+        assert_eq!(string(r#""\x41\x7f""#), "A\x7f");
+        assert_eq!(string(r#""\u{1F600}\u{e9}""#), "😀é");
+        assert_eq!(string("\"héllo 😀\""), "héllo 😀");
+        assert_eq!(string("\"one, \\\n      two\""), "one, two");
+    }
+
+    #[test]
+    fn lexes_raw_strings() {
+        let string = |source| literal(source).to_string();
+        assert_eq!(string(r#"r"C:\games\n""#), r"C:\games\n");
+        assert_eq!(string(r###"r#"she said "hi""#"###), r#"she said "hi""#);
+        assert_eq!(string(r###"r##"a "# b"##"###), r##"a "# b"##);
+        assert_eq!(string("r\"a\nb\""), "a\nb");
+    }
+
+    #[test]
+    fn counts_lines_inside_strings() {
+        let lines = |source| -> Vec<usize> {
+            let tokens = Lexer::new(source).lex().unwrap();
+            tokens.iter().map(|token| token.line).collect()
+        };
+        assert_eq!(lines("\"a\nb\" x"), [1, 2, 2]);
+        assert_eq!(lines("r\"a\n\nb\" x"), [1, 3, 3]);
+        assert_eq!(lines("\"a\\\n\n  b\" x"), [1, 3, 3]);
+    }
+
+    #[test]
+    fn rejects_malformed_strings() {
+        assert_eq!(error(r#""\q""#).0, "Unknown escape sequence '\\q'");
+        assert_eq!(
+            error(r#""\x4""#).0,
+            "Expected two hexadecimal digits after '\\x'"
+        );
+        assert_eq!(
+            error(r#""\x80""#).0,
+            "Escape '\\x80' is out of range, use '\\u{..}' for non-ASCII characters"
+        );
+        assert_eq!(error(r#""\u41""#).0, "Expected '{' after '\\u'");
+        assert_eq!(
+            error(r#""\u{41""#).0,
+            "Unterminated unicode escape, expected '}'"
+        );
+        assert_eq!(error(r#""\u{}""#).0, "Invalid unicode escape '\\u{}'");
+        assert_eq!(
+            error(r#""\u{1234567}""#).0,
+            "Invalid unicode escape '\\u{1234567}'"
+        );
+        assert_eq!(
+            error(r#""\u{D800}""#).0,
+            "Invalid unicode escape '\\u{D800}', not a valid character"
+        );
+        // Unterminated strings are reported where they start.
+        assert_eq!(
+            error("x\n\"abc\n\n"),
+            ("Unterminated string".to_string(), 2)
+        );
+        assert_eq!(
+            error("r#\"abc\"\n"),
+            ("Unterminated raw string".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn lexes_labels_only_before_loops_and_after_break_or_continue() {
+        assert_eq!(
+            types("'outer: while x { break 'outer; }")[..3],
+            [TokenType::Label, TokenType::Colon, TokenType::While]
+        );
+        assert_eq!(
+            types("{ 'a: for i in x { continue 'a; } }")[1..3],
+            [TokenType::Label, TokenType::Colon]
+        );
+        assert_eq!(
+            types("break 'a;"),
+            [
+                TokenType::Break,
+                TokenType::Label,
+                TokenType::Semicolon,
+                TokenType::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn lexes_strings_that_look_like_labels() {
+        for source in [
+            "print 'note: for example';",
+            "let s = 'a:b';",
+            "let s = 'outer: loop';",
+            "x => 'key: v'",
+        ] {
+            assert!(types(source).contains(&TokenType::String), "{}", source);
+            assert!(!types(source).contains(&TokenType::Label), "{}", source);
+        }
+    }
+}
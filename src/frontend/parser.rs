@@ -1,25 +1,75 @@
+use std::{collections::HashMap, path::Path};
+
 use crate::general::{
-    error::{Error, ErrorType},
+    error::{Error, ErrorType, Warning},
     expression::{
-        BinaryExpression, Expression, GroupingExpression, LiteralExpression, UnaryExpression,
-        VariableExpression,
+        ArrayExpression, AssignmentExpression, BinaryExpression, BlockExpression, CallExpression,
+        DestructureExpression, Expression, FormatExpression, FormatPart, GetExpression,
+        GroupingExpression, IfExpression, IndexExpression, IndexSetExpression, LambdaExpression,
+        LiteralExpression, LogicalExpression, LoopExpression, MapExpression, MatchArm,
+        MatchExpression, PathExpression, RangeExpression, SetExpression, StructExpression,
+        TryExpression, TupleExpression, UnaryExpression, VariableExpression,
     },
+    format::FormatSpec,
     object::Object,
+    pattern::{Coverage, Pattern},
     statement::{
-        AssignmentExpression, BlockStatement, ExpressionStatement, IfStatement, LetStatement,
-        PrintStatement, Statement, WhileStatement,
+        BreakStatement, ConstStatement, ContinueStatement, EnumStatement, ExpressionStatement,
+        ForStatement, FunctionStatement, ImplStatement, LetStatement, PrintStatement,
+        ReturnStatement, Statement, StructStatement, UseStatement, Variant, WhileStatement,
     },
     token::{Token, TokenType},
 };
 
+// What a name in scope was declared as, so assignments to it can be
+// checked before anything runs.
+#[derive(Debug, Clone, Copy)]
+struct Declaration {
+    kind: &'static str,
+    mutable: bool,
+    line: usize,
+}
+
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    function_depth: usize,
+    // Labels of the enclosing loops, and whether 'break' may give each of
+    // them a value, which only 'loop' allows.
+    loop_labels: Vec<(Option<String>, bool)>,
+    struct_literals: bool,
+    // Variant names of every enum declared so far, used to check whether a
+    // match over them is exhaustive.
+    enums: HashMap<String, Vec<String>>,
+    // Names declared in each enclosing scope, innermost last. Names missing
+    // here, like globals declared further down, are left to the interpreter.
+    scopes: Vec<HashMap<String, Declaration>>,
+    // Top level names declared with 'pub', which importing modules can use.
+    exports: Vec<Token>,
+    warnings: Vec<Warning>,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        Self {
+            tokens,
+            current: 0,
+            function_depth: 0,
+            loop_labels: Vec::new(),
+            struct_literals: true,
+            enums: HashMap::new(),
+            scopes: vec![HashMap::new()],
+            exports: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    pub fn exports(&self) -> &[Token] {
+        &self.exports
     }
 
     pub fn parse(&mut self) -> Result<Vec<Statement>, Error> {
@@ -33,28 +83,59 @@ impl Parser {
     fn statement(&mut self) -> Result<Statement, Error> {
         match self.peek().ttype {
             TokenType::Let => self.let_statement(),
-            TokenType::Identifier => self.assignment_statement(),
-            TokenType::OpenCurly => self.block_statement(),
-            TokenType::If => self.if_statement(),
-            TokenType::While => self.while_statement(),
+            TokenType::Const => self.const_statement(),
+            TokenType::While => self.while_statement(None),
+            TokenType::For => self.for_statement(None),
+            TokenType::Label => self.labeled_statement(),
             TokenType::Print => self.print_statement(),
+            TokenType::Fn => self.function_statement(),
+            TokenType::Struct => self.struct_statement(),
+            TokenType::Enum => self.enum_statement(),
+            TokenType::Impl => self.impl_statement(),
+            TokenType::Return => self.return_statement(),
+            TokenType::Break => self.break_statement(),
+            TokenType::Continue => self.continue_statement(),
+            TokenType::Pub => self.pub_statement(),
+            TokenType::Mod => self.mod_statement(),
+            TokenType::Use => self.use_statement(),
             _ => self.expression_statement(),
         }
     }
 
     fn let_statement(&mut self) -> Result<Statement, Error> {
-        self.advance();
-        let identifier = self.consume(
+        let keyword = self.next_token();
+        let mutable = self.does_match(&[TokenType::Mut]);
+        if mutable {
+            self.advance();
+        }
+        if !self.does_match(&[
             TokenType::Identifier,
-            &format!(
-                "Expected identifier after 'let', found '{}'",
+            TokenType::OpenParen,
+            TokenType::OpenBracket,
+        ]) {
+            return Err(self.error(&format!(
+                "Expected identifier or pattern after 'let', found '{}'",
                 self.peek().lexeme
-            ),
-        )?;
+            )));
+        }
+        let pattern = self.pattern()?;
         let mut value = Expression::Literal(LiteralExpression::new(Object::Nil));
         if self.does_match(&[TokenType::Equal]) {
             self.advance();
             value = self.expression()?;
+        } else if !matches!(pattern, Pattern::Binding(_)) {
+            return Err(self.error(&format!(
+                "Expected '=' after destructuring pattern, found '{}'",
+                self.peek().lexeme
+            )));
+        } else if let (Pattern::Binding(identifier), false) = (&pattern, mutable) {
+            // It could never be given a value, since assigning it later
+            // would be the second assignment to an immutable variable.
+            return Err(self.error(&format!(
+                "Expected '=' after immutable variable '{0}', found '{1}', declare it with 'let mut {0};' to assign it later",
+                identifier.lexeme,
+                self.peek().lexeme
+            )));
         }
         self.consume(
             TokenType::Semicolon,
@@ -63,15 +144,28 @@ impl Parser {
                 self.peek().lexeme
             ),
         )?;
-        Ok(Statement::Let(LetStatement::new(identifier, value)))
+        for identifier in pattern.bindings() {
+            self.declare(identifier, "variable", mutable)?;
+        }
+        Ok(Statement::Let(LetStatement::new(
+            keyword, mutable, pattern, value,
+        )))
     }
 
-    fn assignment_statement(&mut self) -> Result<Statement, Error> {
-        let identifier = self.next_token();
+    fn const_statement(&mut self) -> Result<Statement, Error> {
+        let keyword = self.next_token();
+        self.top_level(&keyword)?;
+        let identifier = self.consume(
+            TokenType::Identifier,
+            &format!(
+                "Expected constant name after 'const', found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
         self.consume(
             TokenType::Equal,
             &format!(
-                "Expected '=' after identifier, found '{}'",
+                "Expected '=' after constant name, found '{}'",
                 self.peek().lexeme
             ),
         )?;
@@ -79,15 +173,280 @@ impl Parser {
         self.consume(
             TokenType::Semicolon,
             &format!(
-                "Expected ';' after variable declaration, found '{}'",
+                "Expected ';' after constant declaration, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        self.declare(&identifier, "constant", false)?;
+        Ok(Statement::Const(ConstStatement::new(identifier, value)))
+    }
+
+    // A constant can be shadowed in an inner scope, but never redeclared
+    // next to itself.
+    fn declare(
+        &mut self,
+        identifier: &Token,
+        kind: &'static str,
+        mutable: bool,
+    ) -> Result<(), Error> {
+        let scope = self.scopes.last_mut().unwrap();
+        if let Some(declaration) = scope.get(&identifier.lexeme) {
+            if declaration.kind == "constant" {
+                return Err(Error::new(
+                    ErrorType::ParsingError,
+                    &format!(
+                        "Can not redeclare constant '{}' declared on line {}",
+                        identifier.lexeme, declaration.line
+                    ),
+                    identifier.line,
+                ));
+            }
+        }
+        let declaration = Declaration {
+            kind,
+            mutable,
+            line: identifier.line,
+        };
+        scope.insert(identifier.lexeme.clone(), declaration);
+        Ok(())
+    }
+
+    fn check_assignable(&self, identifier: &Token) -> Result<(), Error> {
+        let declaration = self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&identifier.lexeme));
+        let message = match declaration {
+            Some(declaration) if declaration.mutable => return Ok(()),
+            None => return Ok(()),
+            Some(declaration) if declaration.kind == "variable" => format!(
+                "Can not assign twice to immutable variable '{0}' declared on line {1}, declare it with 'let mut {0}' to allow it",
+                identifier.lexeme, declaration.line
+            ),
+            Some(declaration) => format!(
+                "Can not assign to {} '{}' declared on line {}",
+                declaration.kind, identifier.lexeme, declaration.line
+            ),
+        };
+        Err(Error::new(
+            ErrorType::ParsingError,
+            &message,
+            identifier.line,
+        ))
+    }
+
+    fn scoped<T>(&mut self, body: impl FnOnce(&mut Self) -> Result<T, Error>) -> Result<T, Error> {
+        self.scopes.push(HashMap::new());
+        let result = body(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn assignment_pattern(&self, target: Expression, equal: &Token) -> Result<Pattern, Error> {
+        let patterns = |elements: Vec<Expression>| {
+            elements
+                .into_iter()
+                .map(|element| self.assignment_pattern(element, equal))
+                .collect::<Result<Vec<Pattern>, Error>>()
+        };
+        match target {
+            Expression::Variable(variable) if variable.identifier.lexeme == "_" => {
+                Ok(Pattern::Wildcard)
+            }
+            Expression::Variable(variable) => Ok(Pattern::Binding(variable.identifier)),
+            Expression::Tuple(tuple) => Ok(Pattern::Tuple(patterns(tuple.elements)?)),
+            Expression::Array(array) => Ok(Pattern::Array {
+                head: patterns(array.elements)?,
+                rest: None,
+                tail: Vec::new(),
+            }),
+            _ => Err(Error::new(
+                ErrorType::ParsingError,
+                "Invalid assignment target",
+                equal.line,
+            )),
+        }
+    }
+
+    fn pub_statement(&mut self) -> Result<Statement, Error> {
+        let keyword = self.next_token();
+        self.top_level(&keyword)?;
+        let statement = match self.peek().ttype {
+            TokenType::Fn
+            | TokenType::Struct
+            | TokenType::Enum
+            | TokenType::Let
+            | TokenType::Const
+            | TokenType::Mod => self.statement()?,
+            _ => {
+                return Err(self.error(&format!(
+                "Expected 'fn', 'struct', 'enum', 'let', 'const' or 'mod' after 'pub', found '{}'",
+                self.peek().lexeme
+            )))
+            }
+        };
+        match &statement {
+            Statement::Function(function) => self.exports.push(function.identifier.clone()),
+            Statement::Struct(structure) => self.exports.push(structure.identifier.clone()),
+            Statement::Enum(enumeration) => self.exports.push(enumeration.identifier.clone()),
+            Statement::Let(statement) => self
+                .exports
+                .extend(statement.pattern.bindings().into_iter().cloned()),
+            Statement::Const(constant) => self.exports.push(constant.identifier.clone()),
+            Statement::Use(statement) => self.exports.extend(statement.module.clone()),
+            _ => {}
+        }
+        Ok(statement)
+    }
+
+    // `mod math;` is short for `use "math.dvr";`.
+    fn mod_statement(&mut self) -> Result<Statement, Error> {
+        let keyword = self.next_token();
+        self.top_level(&keyword)?;
+        let identifier = self.consume(
+            TokenType::Identifier,
+            &format!(
+                "Expected module name after 'mod', found '{}'",
                 self.peek().lexeme
             ),
         )?;
-        Ok(Statement::Assignment(AssignmentExpression::new(
-            identifier, value,
+        self.consume(
+            TokenType::Semicolon,
+            &format!(
+                "Expected ';' after module name, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        self.declare(&identifier, "module", false)?;
+        let path = format!("{}.dvr", identifier.lexeme);
+        Ok(Statement::Use(UseStatement::new(
+            keyword,
+            path,
+            Some(identifier),
+            Vec::new(),
+        )))
+    }
+
+    // Either `use "lib/math.dvr";`, which binds the module under the name of
+    // its file, or a path like `use lib::math::{gcd, lcm};` whose last part
+    // names the items to import from 'lib/math.dvr'.
+    fn use_statement(&mut self) -> Result<Statement, Error> {
+        let keyword = self.next_token();
+        self.top_level(&keyword)?;
+        let (path, module, items) = if self.does_match(&[TokenType::String]) {
+            let file = self.next_token();
+            let path = file.literal.to_string();
+            let name = Path::new(&path)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default();
+            let mut characters = name.chars();
+            let is_identifier = characters
+                .next()
+                .is_some_and(|first| first.is_alphabetic() || first == '_')
+                && characters.all(|character| character.is_alphanumeric() || character == '_');
+            if !is_identifier {
+                return Err(self.error(&format!(
+                    "Can not name a module after the file '{}', rename it to a valid identifier",
+                    path
+                )));
+            }
+            let module = Token::new(TokenType::Identifier, name, Object::Nil, file.line);
+            (path, Some(module), Vec::new())
+        } else {
+            let mut segments = vec![self.consume(
+                TokenType::Identifier,
+                &format!(
+                    "Expected module path or file name after 'use', found '{}'",
+                    self.peek().lexeme
+                ),
+            )?];
+            let mut items = None;
+            while self.does_match(&[TokenType::ColonColon]) {
+                self.advance();
+                if self.does_match(&[TokenType::OpenCurly]) {
+                    items = Some(self.use_items()?);
+                    break;
+                }
+                segments.push(self.consume(
+                    TokenType::Identifier,
+                    &format!(
+                        "Expected item name or '{{' after '::', found '{}'",
+                        self.peek().lexeme
+                    ),
+                )?);
+            }
+            let (module, items) = match items {
+                Some(items) => (None, items),
+                None if segments.len() == 1 => (segments.pop(), Vec::new()),
+                None => (None, vec![segments.pop().unwrap()]),
+            };
+            let path = match &module {
+                Some(module) => format!("{}.dvr", module.lexeme),
+                None => {
+                    let parts: Vec<&str> = segments
+                        .iter()
+                        .map(|segment| segment.lexeme.as_str())
+                        .collect();
+                    format!("{}.dvr", parts.join("/"))
+                }
+            };
+            (path, module, items)
+        };
+        self.consume(
+            TokenType::Semicolon,
+            &format!("Expected ';' after 'use', found '{}'", self.peek().lexeme),
+        )?;
+        if let Some(module) = &module {
+            self.declare(module, "module", false)?;
+        }
+        for item in &items {
+            self.declare(item, "import", false)?;
+        }
+        Ok(Statement::Use(UseStatement::new(
+            keyword, path, module, items,
         )))
     }
 
+    fn use_items(&mut self) -> Result<Vec<Token>, Error> {
+        self.advance();
+        let mut items: Vec<Token> = Vec::new();
+        while !self.does_match(&[TokenType::CloseCurly]) {
+            let item = self.consume(
+                TokenType::Identifier,
+                &format!("Expected item name, found '{}'", self.peek().lexeme),
+            )?;
+            if items.iter().any(|other| other.lexeme == item.lexeme) {
+                return Err(self.error(&format!("Item '{}' is imported twice", item.lexeme)));
+            }
+            items.push(item);
+            if !self.does_match(&[TokenType::Comma]) {
+                break;
+            }
+            self.advance();
+        }
+        self.consume(
+            TokenType::CloseCurly,
+            &format!(
+                "Expected '}}' after imported items, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        Ok(items)
+    }
+
+    fn top_level(&self, keyword: &Token) -> Result<(), Error> {
+        if self.scopes.len() > 1 {
+            return Err(Error::new(
+                ErrorType::ParsingError,
+                &format!("Can only use '{}' at the top level", keyword.lexeme),
+                keyword.line,
+            ));
+        }
+        Ok(())
+    }
+
     fn print_statement(&mut self) -> Result<Statement, Error> {
         self.advance();
         let mut expressions = Vec::new();
@@ -106,63 +465,677 @@ impl Parser {
         Ok(Statement::Print(PrintStatement::new(expressions)))
     }
 
-    fn if_statement(&mut self) -> Result<Statement, Error> {
+    fn function_statement(&mut self) -> Result<Statement, Error> {
+        let function = self.function()?;
+        self.declare(&function.identifier, "function", false)?;
+        Ok(Statement::Function(function))
+    }
+
+    fn function(&mut self) -> Result<FunctionStatement, Error> {
         self.advance();
-        let conditional = self.expression()?;
-        let if_block = self.block_statement()?;
-        let mut else_block = None;
-        if self.peek().ttype == TokenType::Else {
-            self.advance();
-            if self.peek().ttype == TokenType::If {
-                else_block = Some(self.if_statement()?);
-            } else {
-                else_block = Some(self.block_statement()?);
+        let identifier = self.consume(
+            TokenType::Identifier,
+            &format!(
+                "Expected function name after 'fn', found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        self.consume(
+            TokenType::OpenParen,
+            &format!(
+                "Expected '(' after function name, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        let parameters = self.parameters(TokenType::CloseParen)?;
+        self.consume(
+            TokenType::CloseParen,
+            &format!(
+                "Expected ')' after parameters, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        let body = self.function_body(&parameters, |parser| parser.function_block());
+        Ok(FunctionStatement::new(identifier, parameters, body?))
+    }
+
+    fn enum_statement(&mut self) -> Result<Statement, Error> {
+        self.advance();
+        let identifier = self.consume(
+            TokenType::Identifier,
+            &format!(
+                "Expected enum name after 'enum', found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        self.consume(
+            TokenType::OpenCurly,
+            &format!(
+                "Expected '{{' after enum name, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        let mut variants: Vec<Variant> = Vec::new();
+        while !self.does_match(&[TokenType::CloseCurly]) {
+            let variant = self.consume(
+                TokenType::Identifier,
+                &format!("Expected variant name, found '{}'", self.peek().lexeme),
+            )?;
+            if variants
+                .iter()
+                .any(|other| other.identifier.lexeme == variant.lexeme)
+            {
+                return Err(self.error(&format!(
+                    "Variant '{}' is already declared in enum '{}'",
+                    variant.lexeme, identifier.lexeme
+                )));
             }
+            let mut fields = None;
+            if self.does_match(&[TokenType::OpenParen]) {
+                self.advance();
+                fields = Some(self.parameters(TokenType::CloseParen)?);
+                self.consume(
+                    TokenType::CloseParen,
+                    &format!(
+                        "Expected ')' after variant fields, found '{}'",
+                        self.peek().lexeme
+                    ),
+                )?;
+            }
+            variants.push(Variant::new(variant, fields));
+            if !self.does_match(&[TokenType::Comma]) {
+                break;
+            }
+            self.advance();
         }
-        Ok(Statement::If(IfStatement::new(
-            conditional,
-            if_block,
-            else_block,
-        )))
+        self.consume(
+            TokenType::CloseCurly,
+            &format!(
+                "Expected '}}' after enum variants, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        self.enums.insert(
+            identifier.lexeme.clone(),
+            variants
+                .iter()
+                .map(|variant| variant.identifier.lexeme.clone())
+                .collect(),
+        );
+        self.declare(&identifier, "enum", false)?;
+        Ok(Statement::Enum(EnumStatement::new(identifier, variants)))
     }
 
-    fn while_statement(&mut self) -> Result<Statement, Error> {
+    fn impl_statement(&mut self) -> Result<Statement, Error> {
         self.advance();
-        let conditional = self.expression()?;
-        let block = self.block_statement()?;
-        Ok(Statement::While(WhileStatement::new(conditional, block)))
+        let identifier = self.consume(
+            TokenType::Identifier,
+            &format!(
+                "Expected type name after 'impl', found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        self.consume(
+            TokenType::OpenCurly,
+            &format!(
+                "Expected '{{' after type name, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        let mut methods: Vec<FunctionStatement> = Vec::new();
+        while self.does_match(&[TokenType::Fn]) {
+            let method = self.function()?;
+            if methods
+                .iter()
+                .any(|other| other.identifier.lexeme == method.identifier.lexeme)
+            {
+                return Err(Error::new(
+                    ErrorType::ParsingError,
+                    &format!(
+                        "Method '{}' is already defined for '{}'",
+                        method.identifier.lexeme, identifier.lexeme
+                    ),
+                    method.identifier.line,
+                ));
+            }
+            methods.push(method);
+        }
+        self.consume(
+            TokenType::CloseCurly,
+            &format!(
+                "Expected 'fn' or '}}' in impl block, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        Ok(Statement::Impl(ImplStatement::new(identifier, methods)))
+    }
+
+    // Unlike `let` bindings, parameters can be assigned to.
+    fn function_body(
+        &mut self,
+        parameters: &[Token],
+        body: impl FnOnce(&mut Self) -> Result<Vec<Statement>, Error>,
+    ) -> Result<Vec<Statement>, Error> {
+        let loop_labels = std::mem::take(&mut self.loop_labels);
+        self.function_depth += 1;
+        let body = self.scoped(|parser| {
+            for parameter in parameters {
+                parser.declare(parameter, "parameter", true)?;
+            }
+            body(parser)
+        });
+        self.function_depth -= 1;
+        self.loop_labels = loop_labels;
+        body
+    }
+
+    fn parameters(&mut self, terminator: TokenType) -> Result<Vec<Token>, Error> {
+        let mut parameters: Vec<Token> = Vec::new();
+        while self.peek().ttype != terminator {
+            let parameter = self.consume(
+                TokenType::Identifier,
+                &format!("Expected parameter name, found '{}'", self.peek().lexeme),
+            )?;
+            if parameters
+                .iter()
+                .any(|other| other.lexeme == parameter.lexeme)
+            {
+                return Err(self.error(&format!("Duplicate parameter '{}'", parameter.lexeme)));
+            }
+            parameters.push(parameter);
+            if !self.does_match(&[TokenType::Comma]) {
+                break;
+            }
+            self.advance();
+        }
+        Ok(parameters)
     }
 
-    fn block_statement(&mut self) -> Result<Statement, Error> {
+    fn struct_statement(&mut self) -> Result<Statement, Error> {
         self.advance();
-        let mut statements = Vec::new();
-        while !self.does_match(&[TokenType::CloseCurly]) && !self.is_eof() {
-            statements.push(self.statement()?);
+        let identifier = self.consume(
+            TokenType::Identifier,
+            &format!(
+                "Expected struct name after 'struct', found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        self.consume(
+            TokenType::OpenCurly,
+            &format!(
+                "Expected '{{' after struct name, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        let mut fields: Vec<Token> = Vec::new();
+        while !self.does_match(&[TokenType::CloseCurly]) {
+            let field = self.consume(
+                TokenType::Identifier,
+                &format!("Expected field name, found '{}'", self.peek().lexeme),
+            )?;
+            if fields.iter().any(|other| other.lexeme == field.lexeme) {
+                return Err(self.error(&format!(
+                    "Field '{}' is already declared in struct '{}'",
+                    field.lexeme, identifier.lexeme
+                )));
+            }
+            fields.push(field);
+            if !self.does_match(&[TokenType::Comma]) {
+                break;
+            }
+            self.advance();
         }
         self.consume(
             TokenType::CloseCurly,
             &format!(
-                "Expected '}}' after if_block, found '{}'",
+                "Expected '}}' after struct fields, found '{}'",
                 self.peek().lexeme
             ),
         )?;
-        Ok(Statement::Block(BlockStatement::new(statements)))
+        self.declare(&identifier, "struct", false)?;
+        Ok(Statement::Struct(StructStatement::new(identifier, fields)))
     }
 
-    fn expression_statement(&mut self) -> Result<Statement, Error> {
-        let expression = self.expression()?;
+    fn return_statement(&mut self) -> Result<Statement, Error> {
+        let keyword = self.next_token();
+        if self.function_depth == 0 {
+            return Err(Error::new(
+                ErrorType::ParsingError,
+                "Can not use 'return' outside of a function",
+                keyword.line,
+            ));
+        }
+        let mut value = None;
+        if !self.does_match(&[TokenType::Semicolon]) {
+            value = Some(self.expression()?);
+        }
         self.consume(
             TokenType::Semicolon,
             &format!(
-                "Expected ';' after expression, found '{}'",
+                "Expected ';' after return value, found '{}'",
                 self.peek().lexeme
             ),
         )?;
+        Ok(Statement::Return(ReturnStatement::new(value)))
+    }
+
+    fn break_statement(&mut self) -> Result<Statement, Error> {
+        let keyword = self.next_token();
+        let label = self.loop_label(&keyword)?;
+        let mut value = None;
+        if !self.does_match(&[TokenType::Semicolon]) {
+            let target = match &label {
+                Some(label) => self
+                    .loop_labels
+                    .iter()
+                    .rev()
+                    .find(|(name, _)| name.as_ref() == Some(&label.lexeme)),
+                None => self.loop_labels.last(),
+            };
+            if !target.is_some_and(|(_, breaks_with_value)| *breaks_with_value) {
+                return Err(self.error("Can not break with a value from a 'while' or 'for' loop"));
+            }
+            value = Some(self.expression()?);
+        }
+        self.consume(
+            TokenType::Semicolon,
+            &format!("Expected ';' after 'break', found '{}'", self.peek().lexeme),
+        )?;
+        Ok(Statement::Break(BreakStatement::new(label, value)))
+    }
+
+    fn continue_statement(&mut self) -> Result<Statement, Error> {
+        let keyword = self.next_token();
+        let label = self.loop_label(&keyword)?;
+        self.consume(
+            TokenType::Semicolon,
+            &format!(
+                "Expected ';' after 'continue', found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        Ok(Statement::Continue(ContinueStatement::new(label)))
+    }
+
+    fn loop_label(&mut self, keyword: &Token) -> Result<Option<Token>, Error> {
+        if self.loop_labels.is_empty() {
+            return Err(Error::new(
+                ErrorType::ParsingError,
+                &format!("Can not use '{}' outside of a loop", keyword.lexeme),
+                keyword.line,
+            ));
+        }
+        if !self.does_match(&[TokenType::Label]) {
+            return Ok(None);
+        }
+        let label = self.next_token();
+        if !self
+            .loop_labels
+            .iter()
+            .any(|(name, _)| name.as_ref() == Some(&label.lexeme))
+        {
+            return Err(Error::new(
+                ErrorType::ParsingError,
+                &format!("Use of undeclared label '{}'", &label.lexeme[1..]),
+                label.line,
+            ));
+        }
+        Ok(Some(label))
+    }
+
+    fn labeled_statement(&mut self) -> Result<Statement, Error> {
+        let label = self.next_token();
+        self.consume(
+            TokenType::Colon,
+            &format!("Expected ':' after label, found '{}'", self.peek().lexeme),
+        )?;
+        match self.peek().ttype {
+            TokenType::While => self.while_statement(Some(label)),
+            TokenType::For => self.for_statement(Some(label)),
+            TokenType::Loop => {
+                let expression = self.loop_expression(Some(label))?;
+                self.block_like_statement(expression)
+            }
+            _ => Err(self.error(&format!(
+                "Expected a loop after label '{}', found '{}'",
+                &label.lexeme[1..],
+                self.peek().lexeme
+            ))),
+        }
+    }
+
+    fn while_statement(&mut self, label: Option<Token>) -> Result<Statement, Error> {
+        self.advance();
+        let conditional = self.condition()?;
+        let block = self.loop_body(&label, false)?;
+        Ok(Statement::While(WhileStatement::new(
+            label,
+            conditional,
+            block,
+        )))
+    }
+
+    fn for_statement(&mut self, label: Option<Token>) -> Result<Statement, Error> {
+        self.advance();
+        let variable = self.consume(
+            TokenType::Identifier,
+            &format!(
+                "Expected loop variable after 'for', found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        self.consume(
+            TokenType::In,
+            &format!(
+                "Expected 'in' after loop variable, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        let iterable = self.condition()?;
+        let block = self.scoped(|parser| {
+            parser.declare(&variable, "loop variable", false)?;
+            parser.loop_body(&label, false)
+        })?;
+        Ok(Statement::For(ForStatement::new(
+            label, variable, iterable, block,
+        )))
+    }
+
+    fn loop_body(
+        &mut self,
+        label: &Option<Token>,
+        breaks_with_value: bool,
+    ) -> Result<BlockExpression, Error> {
+        self.loop_labels.push((
+            label.as_ref().map(|label| label.lexeme.clone()),
+            breaks_with_value,
+        ));
+        let block = self.block_expression();
+        self.loop_labels.pop();
+        block
+    }
+
+    fn loop_expression(&mut self, label: Option<Token>) -> Result<Expression, Error> {
+        self.advance();
+        let body = self.loop_body(&label, true)?;
+        Ok(Expression::Loop(LoopExpression::new(label, body)))
+    }
+
+    fn try_expression(&mut self) -> Result<Expression, Error> {
+        self.advance();
+        let body = self.block_expression()?;
+        self.consume(
+            TokenType::Catch,
+            &format!(
+                "Expected 'catch' after try block, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        let mut binding = None;
+        if self.does_match(&[TokenType::Identifier]) {
+            binding = Some(self.next_token());
+        }
+        let handler = self.scoped(|parser| {
+            if let Some(binding) = &binding {
+                parser.declare(binding, "caught error", false)?;
+            }
+            parser.block_expression()
+        })?;
+        Ok(Expression::Try(TryExpression::new(body, binding, handler)))
+    }
+
+    fn if_expression(&mut self) -> Result<Expression, Error> {
+        self.advance();
+        let condition = self.condition()?;
+        let then_branch = self.block_expression()?;
+        let mut else_branch = None;
+        if self.does_match(&[TokenType::Else]) {
+            self.advance();
+            else_branch = Some(if self.does_match(&[TokenType::If]) {
+                self.if_expression()?
+            } else {
+                Expression::Block(self.block_expression()?)
+            });
+        }
+        Ok(Expression::If(IfExpression::new(
+            condition,
+            then_branch,
+            else_branch,
+        )))
+    }
+
+    fn block_expression(&mut self) -> Result<BlockExpression, Error> {
+        self.consume(
+            TokenType::OpenCurly,
+            &format!("Expected '{{' before block, found '{}'", self.peek().lexeme),
+        )?;
+        let mut statements = self.scoped(|parser| {
+            parser.delimited(|parser| {
+                let mut statements = Vec::new();
+                while !parser.does_match(&[TokenType::CloseCurly]) && !parser.is_eof() {
+                    statements.push(parser.statement()?);
+                }
+                Ok(statements)
+            })
+        })?;
+        // A final expression statement without a semicolon is the value of
+        // the block.
+        let mut tail = None;
+        if matches!(statements.last(), Some(Statement::Expression(_)))
+            && self.tokens[self.current - 1].ttype != TokenType::Semicolon
+        {
+            if let Some(Statement::Expression(statement)) = statements.pop() {
+                tail = Some(statement.expression);
+            }
+        }
+        self.consume(
+            TokenType::CloseCurly,
+            &format!("Expected '}}' after block, found '{}'", self.peek().lexeme),
+        )?;
+        Ok(BlockExpression::new(statements, tail))
+    }
+
+    // A function body gives the value of its trailing expression, just as
+    // if it was returned.
+    fn function_block(&mut self) -> Result<Vec<Statement>, Error> {
+        let block = self.block_expression()?;
+        let mut statements = block.statements;
+        if let Some(tail) = block.tail {
+            statements.push(Statement::Return(ReturnStatement::new(Some(*tail))));
+        }
+        Ok(statements)
+    }
+
+    // Struct literals are not allowed directly inside the condition of
+    // 'if', 'while' and 'for', since `while x { ... }` would otherwise read
+    // as constructing a struct named 'x'.
+    fn condition(&mut self) -> Result<Expression, Error> {
+        let struct_literals = std::mem::replace(&mut self.struct_literals, false);
+        let condition = self.expression();
+        self.struct_literals = struct_literals;
+        condition
+    }
+
+    // The lexer has already split the format string into literal parts and
+    // the tokens of each interpolated expression.
+    fn format_string(&mut self) -> Result<Expression, Error> {
+        self.advance();
+        let mut parts = Vec::new();
+        loop {
+            let token = self.next_token();
+            match token.ttype {
+                TokenType::FormatEnd => break,
+                TokenType::String => parts.push(FormatPart::Literal(token.lexeme)),
+                TokenType::InterpolationStart => {
+                    if self.does_match(&[TokenType::InterpolationEnd]) {
+                        return Err(self.error("Expected expression inside '{}' in format string"));
+                    }
+                    let expression = self.delimited(Self::expression)?;
+                    let brace = self.consume(
+                        TokenType::InterpolationEnd,
+                        &format!(
+                            "Expected '}}' after interpolated expression, found '{}'",
+                            self.peek().lexeme
+                        ),
+                    )?;
+                    let text = brace.literal.to_string();
+                    let spec = FormatSpec::parse(&text).ok_or_else(|| {
+                        Error::new(
+                            ErrorType::ParsingError,
+                            &format!("Invalid format spec ':{}'", text),
+                            brace.line,
+                        )
+                    })?;
+                    parts.push(FormatPart::Interpolation {
+                        expression: Box::new(expression),
+                        spec,
+                        brace,
+                    });
+                }
+                _ => unreachable!("format strings only contain literals and interpolations"),
+            }
+        }
+        Ok(Expression::Format(FormatExpression::new(parts)))
+    }
+
+    // Inside any kind of brackets struct literals are unambiguous again.
+    fn delimited<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let struct_literals = std::mem::replace(&mut self.struct_literals, true);
+        let result = parse(self);
+        self.struct_literals = struct_literals;
+        result
+    }
+
+    // As in Rust, a statement starting with a block, 'if', 'loop' or
+    // 'match' ends at its closing brace. The last expression of a block
+    // needs no semicolon either, it becomes the value of the block.
+    fn expression_statement(&mut self) -> Result<Statement, Error> {
+        let expression = match self.peek().ttype {
+            TokenType::OpenCurly => Expression::Block(self.block_expression()?),
+            TokenType::If => self.if_expression()?,
+            TokenType::Loop => self.loop_expression(None)?,
+            TokenType::Match => self.match_expression()?,
+            TokenType::Try => self.try_expression()?,
+            _ => {
+                let expression = self.expression()?;
+                if !self.does_match(&[TokenType::CloseCurly]) {
+                    self.consume(
+                        TokenType::Semicolon,
+                        &format!(
+                            "Expected ';' after expression, found '{}'",
+                            self.peek().lexeme
+                        ),
+                    )?;
+                }
+                return Ok(Statement::Expression(ExpressionStatement::new(expression)));
+            }
+        };
+        self.block_like_statement(expression)
+    }
+
+    fn block_like_statement(&mut self, expression: Expression) -> Result<Statement, Error> {
+        if self.does_match(&[TokenType::Semicolon]) {
+            self.advance();
+        }
         Ok(Statement::Expression(ExpressionStatement::new(expression)))
     }
 
     fn expression(&mut self) -> Result<Expression, Error> {
-        self.logical_expression()
+        self.assignment()
+    }
+
+    // Assignment is right associative and gives the assigned value, so
+    // `a = b = 0` sets both. Only variables, fields, indexes and, for a
+    // plain '=', tuple and array patterns of variables can be assigned to.
+    fn assignment(&mut self) -> Result<Expression, Error> {
+        let target = self.range()?;
+        let operator = match self.peek().ttype {
+            TokenType::Equal => None,
+            TokenType::PlusEqual => Some(TokenType::Plus),
+            TokenType::MinusEqual => Some(TokenType::Minus),
+            TokenType::MultiplicationEqual => Some(TokenType::Multiplication),
+            TokenType::DivisionEqual => Some(TokenType::Division),
+            TokenType::ModuloEqual => Some(TokenType::Modulo),
+            TokenType::BitwiseAndEqual => Some(TokenType::BitwiseAnd),
+            TokenType::BitwiseOrEqual => Some(TokenType::BitwiseOr),
+            TokenType::BitwiseXorEqual => Some(TokenType::BitwiseXor),
+            TokenType::ShiftLeftEqual => Some(TokenType::ShiftLeft),
+            TokenType::ShiftRightEqual => Some(TokenType::ShiftRight),
+            _ => return Ok(target),
+        };
+        let equal = self.next_token();
+        // The operator of `+=` is the '+' of a binary expression, so
+        // `x += 1` works exactly like `x = x + 1`.
+        let operator = operator.map(|ttype| {
+            Token::new(
+                ttype,
+                equal.lexeme.trim_end_matches('='),
+                Object::Nil,
+                equal.line,
+            )
+        });
+        let value = self.assignment()?;
+        match target {
+            Expression::Variable(variable) => {
+                self.check_assignable(&variable.identifier)?;
+                Ok(Expression::Assignment(AssignmentExpression::new(
+                    variable.identifier,
+                    operator,
+                    value,
+                )))
+            }
+            Expression::Index(index) => Ok(Expression::IndexSet(IndexSetExpression::new(
+                *index.object,
+                index.bracket,
+                *index.index,
+                operator,
+                value,
+            ))),
+            Expression::Get(get) if get.identifier.ttype == TokenType::Identifier => {
+                Ok(Expression::Set(SetExpression::new(
+                    *get.object,
+                    get.identifier,
+                    operator,
+                    value,
+                )))
+            }
+            Expression::Tuple(_) | Expression::Array(_) if operator.is_none() => {
+                let pattern = self.assignment_pattern(target, &equal)?;
+                self.check_bindings(&pattern, equal.line)?;
+                for identifier in pattern.bindings() {
+                    self.check_assignable(identifier)?;
+                }
+                Ok(Expression::Destructure(DestructureExpression::new(
+                    pattern, equal, value,
+                )))
+            }
+            Expression::Tuple(_) | Expression::Array(_) => Err(Error::new(
+                ErrorType::ParsingError,
+                &format!("Can not use '{}' to destructure, only '='", equal.lexeme),
+                equal.line,
+            )),
+            _ => Err(Error::new(
+                ErrorType::ParsingError,
+                "Invalid assignment target",
+                equal.line,
+            )),
+        }
+    }
+
+    fn range(&mut self) -> Result<Expression, Error> {
+        let start = self.logical_expression()?;
+        if self.does_match(&[TokenType::DotDot, TokenType::DotDotEqual]) {
+            let operator = self.next_token();
+            let end = self.logical_expression()?;
+            Ok(Expression::Range(RangeExpression::new(
+                start, operator, end,
+            )))
+        } else {
+            Ok(start)
+        }
     }
 
     fn logical_expression(&mut self) -> Result<Expression, Error> {
@@ -174,7 +1147,7 @@ impl Parser {
         while self.does_match(&[TokenType::Or]) {
             let operator = self.next_token();
             let right = self.logical_and()?;
-            left = Expression::Binary(BinaryExpression::new(left, operator, right));
+            left = Expression::Logical(LogicalExpression::new(left, operator, right));
         }
         Ok(left)
     }
@@ -184,13 +1157,13 @@ impl Parser {
         while self.does_match(&[TokenType::And]) {
             let operator = self.next_token();
             let right = self.comparison()?;
-            left = Expression::Binary(BinaryExpression::new(left, operator, right));
+            left = Expression::Logical(LogicalExpression::new(left, operator, right));
         }
         Ok(left)
     }
 
     fn comparison(&mut self) -> Result<Expression, Error> {
-        let mut left = self.term()?;
+        let mut left = self.bitwise_or()?;
         while self.does_match(&[
             TokenType::BangEqual,
             TokenType::EqualEqual,
@@ -198,7 +1171,87 @@ impl Parser {
             TokenType::GreaterEqual,
             TokenType::Less,
             TokenType::LessEqual,
+            TokenType::In,
+            TokenType::Not,
         ]) {
+            let operator = self.membership_operator()?;
+            let right = match operator.ttype {
+                TokenType::In | TokenType::NotIn => self.membership_operand()?,
+                _ => self.bitwise_or()?,
+            };
+            left = Expression::Binary(BinaryExpression::new(left, operator, right));
+        }
+        Ok(left)
+    }
+
+    // Turns 'not' 'in' into a single 'not in' operator.
+    fn membership_operator(&mut self) -> Result<Token, Error> {
+        let operator = self.next_token();
+        if operator.ttype != TokenType::Not {
+            return Ok(operator);
+        }
+        self.consume(
+            TokenType::In,
+            &format!("Expected 'in' after 'not', found '{}'", self.peek().lexeme),
+        )?;
+        Ok(Token::new(
+            TokenType::NotIn,
+            "not in",
+            Object::Nil,
+            operator.line,
+        ))
+    }
+
+    // Ranges bind looser than comparisons, but `x in 1..10` should still
+    // test against the whole range.
+    fn membership_operand(&mut self) -> Result<Expression, Error> {
+        let start = self.bitwise_or()?;
+        if self.does_match(&[TokenType::DotDot, TokenType::DotDotEqual]) {
+            let operator = self.next_token();
+            let end = self.bitwise_or()?;
+            Ok(Expression::Range(RangeExpression::new(
+                start, operator, end,
+            )))
+        } else {
+            Ok(start)
+        }
+    }
+
+    // The bitwise operators bind tighter than comparisons but looser than
+    // arithmetic, in the same order as in Rust: shifts, '&', '^', then '|'.
+    fn bitwise_or(&mut self) -> Result<Expression, Error> {
+        let mut left = self.bitwise_xor()?;
+        while self.does_match(&[TokenType::BitwiseOr]) {
+            let operator = self.next_token();
+            let right = self.bitwise_xor()?;
+            left = Expression::Binary(BinaryExpression::new(left, operator, right));
+        }
+        Ok(left)
+    }
+
+    fn bitwise_xor(&mut self) -> Result<Expression, Error> {
+        let mut left = self.bitwise_and()?;
+        while self.does_match(&[TokenType::BitwiseXor]) {
+            let operator = self.next_token();
+            let right = self.bitwise_and()?;
+            left = Expression::Binary(BinaryExpression::new(left, operator, right));
+        }
+        Ok(left)
+    }
+
+    fn bitwise_and(&mut self) -> Result<Expression, Error> {
+        let mut left = self.shift()?;
+        while self.does_match(&[TokenType::BitwiseAnd]) {
+            let operator = self.next_token();
+            let right = self.shift()?;
+            left = Expression::Binary(BinaryExpression::new(left, operator, right));
+        }
+        Ok(left)
+    }
+
+    fn shift(&mut self) -> Result<Expression, Error> {
+        let mut left = self.term()?;
+        while self.does_match(&[TokenType::ShiftLeft, TokenType::ShiftRight]) {
             let operator = self.next_token();
             let right = self.term()?;
             left = Expression::Binary(BinaryExpression::new(left, operator, right));
@@ -221,9 +1274,8 @@ impl Parser {
         while self.does_match(&[
             TokenType::Multiplication,
             TokenType::Division,
+            TokenType::FloorDivision,
             TokenType::Modulo,
-            TokenType::BitwiseAnd,
-            TokenType::BitwiseOr,
         ]) {
             let operator = self.next_token();
             let right = self.unary()?;
@@ -233,17 +1285,99 @@ impl Parser {
     }
 
     fn unary(&mut self) -> Result<Expression, Error> {
-        if self.does_match(&[TokenType::Minus]) {
+        if self.does_match(&[TokenType::Minus, TokenType::Bang, TokenType::BitwiseNot]) {
             let operator = self.next_token();
-            let right = self.primary()?;
+            let right = self.unary()?;
             Ok(Expression::Unray(UnaryExpression::new(operator, right)))
-        } else if self.does_match(&[TokenType::Bang]) {
+        } else {
+            self.power()
+        }
+    }
+
+    // '**' binds tighter than a prefix operator on its left, so `-2 ** 2` is
+    // -4, and is right associative with the exponent parsed as a unary.
+    fn power(&mut self) -> Result<Expression, Error> {
+        let base = self.call()?;
+        if self.does_match(&[TokenType::Power]) {
             let operator = self.next_token();
-            let right = self.primary()?;
-            Ok(Expression::Unray(UnaryExpression::new(operator, right)))
+            let exponent = self.unary()?;
+            Ok(Expression::Binary(BinaryExpression::new(
+                base, operator, exponent,
+            )))
         } else {
-            self.primary()
+            Ok(base)
+        }
+    }
+
+    fn call(&mut self) -> Result<Expression, Error> {
+        let mut expression = self.primary()?;
+        loop {
+            if self.does_match(&[TokenType::OpenParen]) {
+                self.advance();
+                let arguments = self.list(TokenType::CloseParen)?;
+                let paren = self.consume(
+                    TokenType::CloseParen,
+                    &format!(
+                        "Expected ')' after arguments, found '{}'",
+                        self.peek().lexeme
+                    ),
+                )?;
+                expression = Expression::Call(CallExpression::new(expression, paren, arguments));
+            } else if self.does_match(&[TokenType::OpenBracket]) {
+                let bracket = self.next_token();
+                let index = self.delimited(|parser| parser.expression())?;
+                self.consume(
+                    TokenType::CloseBracket,
+                    &format!("Expected ']' after index, found '{}'", self.peek().lexeme),
+                )?;
+                expression = Expression::Index(IndexExpression::new(expression, bracket, index));
+            } else if self.does_match(&[TokenType::Dot]) {
+                self.advance();
+                // Tuple elements are accessed like fields named by their
+                // position, as in `pair.0`.
+                if !self.does_match(&[TokenType::Identifier, TokenType::Number]) {
+                    return Err(self.error(&format!(
+                        "Expected field name or tuple index after '.', found '{}'",
+                        self.peek().lexeme
+                    )));
+                }
+                let identifier = self.next_token();
+                if identifier.ttype == TokenType::Number
+                    && !identifier.lexeme.chars().all(|c| c.is_ascii_digit())
+                {
+                    return Err(Error::new(
+                        ErrorType::ParsingError,
+                        &format!("Invalid tuple index '{}'", identifier.lexeme),
+                        identifier.line,
+                    ));
+                }
+                expression = Expression::Get(GetExpression::new(expression, identifier));
+            } else if self.does_match(&[TokenType::ColonColon]) {
+                self.advance();
+                let identifier = self.consume(
+                    TokenType::Identifier,
+                    &format!("Expected name after '::', found '{}'", self.peek().lexeme),
+                )?;
+                expression = Expression::Path(PathExpression::new(expression, identifier));
+            } else {
+                break;
+            }
         }
+        Ok(expression)
+    }
+
+    fn list(&mut self, terminator: TokenType) -> Result<Vec<Expression>, Error> {
+        self.delimited(|parser| {
+            let mut expressions = Vec::new();
+            while parser.peek().ttype != terminator {
+                expressions.push(parser.expression()?);
+                if !parser.does_match(&[TokenType::Comma]) {
+                    break;
+                }
+                parser.advance();
+            }
+            Ok(expressions)
+        })
     }
 
     fn primary(&mut self) -> Result<Expression, Error> {
@@ -261,10 +1395,54 @@ impl Parser {
             )))
         } else if self.does_match(&[TokenType::Identifier]) {
             self.advance();
-            Ok(Expression::Variable(VariableExpression::new(current_token)))
+            if self.struct_literals && self.does_match(&[TokenType::OpenCurly]) {
+                self.struct_literal(current_token)
+            } else {
+                Ok(Expression::Variable(VariableExpression::new(current_token)))
+            }
+        } else if self.does_match(&[TokenType::OpenBracket]) {
+            self.advance();
+            let elements = self.list(TokenType::CloseBracket)?;
+            self.consume(
+                TokenType::CloseBracket,
+                &format!(
+                    "Expected ']' after array elements, found '{}'",
+                    self.peek().lexeme
+                ),
+            )?;
+            Ok(Expression::Array(ArrayExpression::new(elements)))
+        } else if self.does_match(&[TokenType::OpenCurly]) {
+            self.brace()
+        } else if self.does_match(&[TokenType::If]) {
+            self.if_expression()
+        } else if self.does_match(&[TokenType::Loop]) {
+            self.loop_expression(None)
+        } else if self.does_match(&[TokenType::Try]) {
+            self.try_expression()
+        } else if self.does_match(&[TokenType::BitwiseOr, TokenType::Or]) {
+            self.lambda()
+        } else if self.does_match(&[TokenType::Match]) {
+            self.match_expression()
+        } else if self.does_match(&[TokenType::FormatStart]) {
+            self.format_string()
         } else if self.does_match(&[TokenType::OpenParen]) {
             self.advance();
-            let expressions = self.expression()?;
+            // A comma makes a tuple, so `(x)` is a grouping but `(x,)` and
+            // `()` are tuples.
+            let (mut elements, tuple) = self.delimited(|parser| {
+                let mut elements = Vec::new();
+                let mut tuple = false;
+                while !parser.does_match(&[TokenType::CloseParen]) {
+                    elements.push(parser.expression()?);
+                    if !parser.does_match(&[TokenType::Comma]) {
+                        break;
+                    }
+                    parser.advance();
+                    tuple = true;
+                }
+                let tuple = tuple || elements.is_empty();
+                Ok((elements, tuple))
+            })?;
             self.consume(
                 TokenType::CloseParen,
                 &format!(
@@ -272,12 +1450,496 @@ impl Parser {
                     self.peek().lexeme
                 ),
             )?;
-            Ok(Expression::Grouping(GroupingExpression::new(expressions)))
+            if tuple {
+                Ok(Expression::Tuple(TupleExpression::new(elements)))
+            } else {
+                Ok(Expression::Grouping(GroupingExpression::new(
+                    elements.remove(0),
+                )))
+            }
         } else {
             Err(self.error(&format!("Unexpected '{}'", self.peek().lexeme)))
         }
     }
 
+    // In an expression '{' starts a map when it is empty or when its first
+    // key is followed by ':', and a block otherwise. The key is only scanned,
+    // not parsed, and the scan gives up at the first brace, label or ';', so
+    // nested braces never get looked at more than once.
+    fn brace(&mut self) -> Result<Expression, Error> {
+        if self.tokens[self.current + 1].ttype == TokenType::CloseCurly {
+            return self.map();
+        }
+        let mut depth = 0_usize;
+        let mut map = false;
+        for token in &self.tokens[self.current + 1..] {
+            match token.ttype {
+                TokenType::OpenParen | TokenType::OpenBracket => depth += 1,
+                TokenType::CloseParen | TokenType::CloseBracket => {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                }
+                TokenType::Colon => {
+                    map = depth == 0;
+                    break;
+                }
+                TokenType::OpenCurly
+                | TokenType::CloseCurly
+                | TokenType::Semicolon
+                | TokenType::Label
+                | TokenType::EOF => break,
+                _ => {}
+            }
+        }
+        if map {
+            self.map()
+        } else {
+            Ok(Expression::Block(self.block_expression()?))
+        }
+    }
+
+    fn map(&mut self) -> Result<Expression, Error> {
+        let brace = self.next_token();
+        let entries = self.delimited(|parser| {
+            let mut entries = Vec::new();
+            while !parser.does_match(&[TokenType::CloseCurly]) {
+                let key = parser.expression()?;
+                parser.consume(
+                    TokenType::Colon,
+                    &format!(
+                        "Expected ':' after map key, found '{}'",
+                        parser.peek().lexeme
+                    ),
+                )?;
+                let value = parser.expression()?;
+                entries.push((key, value));
+                if !parser.does_match(&[TokenType::Comma]) {
+                    break;
+                }
+                parser.advance();
+            }
+            Ok(entries)
+        })?;
+        self.consume(
+            TokenType::CloseCurly,
+            &format!(
+                "Expected '}}' after map entries, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        Ok(Expression::Map(MapExpression::new(brace, entries)))
+    }
+
+    fn struct_literal(&mut self, identifier: Token) -> Result<Expression, Error> {
+        self.advance();
+        let fields = self.delimited(|parser| {
+            let mut fields: Vec<(Token, Expression)> = Vec::new();
+            while !parser.does_match(&[TokenType::CloseCurly]) {
+                let field = parser.consume(
+                    TokenType::Identifier,
+                    &format!("Expected field name, found '{}'", parser.peek().lexeme),
+                )?;
+                if fields.iter().any(|(other, _)| other.lexeme == field.lexeme) {
+                    return Err(parser.error(&format!(
+                        "Field '{}' specified more than once",
+                        field.lexeme
+                    )));
+                }
+                // `Point { x, y }` is shorthand for `Point { x: x, y: y }`.
+                let value = if parser.does_match(&[TokenType::Colon]) {
+                    parser.advance();
+                    parser.expression()?
+                } else {
+                    Expression::Variable(VariableExpression::new(field.clone()))
+                };
+                fields.push((field, value));
+                if !parser.does_match(&[TokenType::Comma]) {
+                    break;
+                }
+                parser.advance();
+            }
+            Ok(fields)
+        })?;
+        self.consume(
+            TokenType::CloseCurly,
+            &format!(
+                "Expected '}}' after struct fields, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        Ok(Expression::Struct(StructExpression::new(
+            identifier, fields,
+        )))
+    }
+
+    fn lambda(&mut self) -> Result<Expression, Error> {
+        let mut parameters = Vec::new();
+        if self.next_token().ttype == TokenType::BitwiseOr {
+            parameters = self.parameters(TokenType::BitwiseOr)?;
+            self.consume(
+                TokenType::BitwiseOr,
+                &format!(
+                    "Expected '|' after closure parameters, found '{}'",
+                    self.peek().lexeme
+                ),
+            )?;
+        }
+        let body = self.function_body(&parameters, |parser| {
+            if parser.does_match(&[TokenType::OpenCurly]) {
+                parser.function_block()
+            } else {
+                parser
+                    .expression()
+                    .map(|value| vec![Statement::Return(ReturnStatement::new(Some(value)))])
+            }
+        });
+        Ok(Expression::Lambda(LambdaExpression::new(parameters, body?)))
+    }
+
+    fn match_expression(&mut self) -> Result<Expression, Error> {
+        let keyword = self.next_token();
+        let subject = self.condition()?;
+        self.consume(
+            TokenType::OpenCurly,
+            &format!(
+                "Expected '{{' after match value, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        let arms = self.delimited(|parser| {
+            let mut arms = Vec::new();
+            let mut coverage = Coverage::default();
+            while !parser.does_match(&[TokenType::CloseCurly]) && !parser.is_eof() {
+                let line = parser.peek().line;
+                let arm = parser.match_arm()?;
+                if coverage.covers(&arm.pattern) {
+                    parser.warn("Unreachable match arm", line);
+                }
+                if arm.guard.is_none() {
+                    coverage.add(&arm.pattern);
+                }
+                let block = matches!(arm.body, Expression::Block(_));
+                arms.push(arm);
+                if parser.does_match(&[TokenType::Comma]) {
+                    parser.advance();
+                } else if !block && !parser.does_match(&[TokenType::CloseCurly]) {
+                    return Err(parser.error(&format!(
+                        "Expected ',' after match arm, found '{}'",
+                        parser.peek().lexeme
+                    )));
+                }
+            }
+            if let Some(missing) = coverage.missing(&parser.enums) {
+                parser.warn(
+                    &format!("Match is not exhaustive, {}", missing),
+                    keyword.line,
+                );
+            }
+            Ok(arms)
+        })?;
+        self.consume(
+            TokenType::CloseCurly,
+            &format!(
+                "Expected '}}' after match arms, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        Ok(Expression::Match(MatchExpression::new(
+            keyword, subject, arms,
+        )))
+    }
+
+    fn match_arm(&mut self) -> Result<MatchArm, Error> {
+        let pattern = self.pattern()?;
+        self.scoped(|parser| {
+            for identifier in pattern.bindings() {
+                parser.declare(identifier, "match binding", false)?;
+            }
+            let mut guard = None;
+            if parser.does_match(&[TokenType::If]) {
+                parser.advance();
+                guard = Some(parser.expression()?);
+            }
+            parser.consume(
+                TokenType::FatArrow,
+                &format!(
+                    "Expected '=>' after pattern, found '{}'",
+                    parser.peek().lexeme
+                ),
+            )?;
+            let body = if parser.does_match(&[TokenType::OpenCurly]) {
+                Expression::Block(parser.block_expression()?)
+            } else {
+                parser.expression()?
+            };
+            Ok(MatchArm::new(pattern, guard, body))
+        })
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, Error> {
+        let line = self.peek().line;
+        let mut alternatives = vec![self.pattern_alternative()?];
+        while self.does_match(&[TokenType::BitwiseOr]) {
+            self.advance();
+            alternatives.push(self.pattern_alternative()?);
+        }
+        let pattern = if alternatives.len() == 1 {
+            alternatives.remove(0)
+        } else {
+            Pattern::Or(alternatives)
+        };
+        self.check_bindings(&pattern, line)?;
+        Ok(pattern)
+    }
+
+    fn check_bindings(&self, pattern: &Pattern, line: usize) -> Result<(), Error> {
+        let bindings = pattern.bindings();
+        for (index, binding) in bindings.iter().enumerate() {
+            if bindings[..index]
+                .iter()
+                .any(|other| other.lexeme == binding.lexeme)
+            {
+                return Err(Error::new(
+                    ErrorType::ParsingError,
+                    &format!(
+                        "Identifier '{}' is bound more than once in the same pattern",
+                        binding.lexeme
+                    ),
+                    binding.line,
+                ));
+            }
+        }
+        if let Pattern::Or(alternatives) = pattern {
+            let mut expected: Vec<&str> = bindings.iter().map(|b| b.lexeme.as_str()).collect();
+            expected.sort();
+            for alternative in alternatives {
+                let mut names: Vec<&str> = alternative
+                    .bindings()
+                    .iter()
+                    .map(|binding| binding.lexeme.as_str())
+                    .collect();
+                names.sort();
+                if names != expected {
+                    return Err(Error::new(
+                        ErrorType::ParsingError,
+                        "Every alternative of an or-pattern must bind the same variables",
+                        line,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn pattern_alternative(&mut self) -> Result<Pattern, Error> {
+        if self.does_match(&[TokenType::OpenParen]) {
+            return self.tuple_pattern();
+        }
+        if self.does_match(&[TokenType::OpenBracket]) {
+            return self.array_pattern();
+        }
+        if self.does_match(&[TokenType::Identifier]) {
+            let identifier = self.next_token();
+            if self.does_match(&[TokenType::ColonColon]) {
+                return self.variant_pattern(identifier);
+            }
+            if self.does_match(&[TokenType::OpenParen]) {
+                return Err(self.error(&format!(
+                    "Variant patterns are written as 'Enum::{}(..)'",
+                    identifier.lexeme
+                )));
+            }
+            if identifier.lexeme == "_" {
+                return Ok(Pattern::Wildcard);
+            }
+            return Ok(Pattern::Binding(identifier));
+        }
+        let start = self.pattern_literal()?;
+        if !self.does_match(&[TokenType::DotDot, TokenType::DotDotEqual]) {
+            return Ok(Pattern::Literal(start));
+        }
+        let operator = self.next_token();
+        let end = self.pattern_literal()?;
+        let inclusive = operator.ttype == TokenType::DotDotEqual;
+        let ordered = match (&start, &end) {
+            (Object::String(x), Object::String(y)) => x < y || inclusive && x == y,
+            (_, _) if start.as_float().is_some() && end.as_float().is_some() => start
+                .compare_numbers(&end)
+                .is_some_and(|ordering| ordering.is_lt() || inclusive && ordering.is_eq()),
+            (_, _) => {
+                return Err(Error::new(
+                    ErrorType::ParsingError,
+                    &format!(
+                        "Range patterns need two numbers or two strings, found '{}{}{}'",
+                        start, operator.lexeme, end
+                    ),
+                    operator.line,
+                ))
+            }
+        };
+        if !ordered {
+            return Err(Error::new(
+                ErrorType::ParsingError,
+                &format!(
+                    "Range pattern '{}{}{}' can never match",
+                    start, operator.lexeme, end
+                ),
+                operator.line,
+            ));
+        }
+        Ok(Pattern::Range {
+            start,
+            end,
+            inclusive,
+        })
+    }
+
+    fn tuple_pattern(&mut self) -> Result<Pattern, Error> {
+        self.advance();
+        let mut elements = Vec::new();
+        let mut tuple = false;
+        while !self.does_match(&[TokenType::CloseParen]) {
+            elements.push(self.pattern()?);
+            if !self.does_match(&[TokenType::Comma]) {
+                break;
+            }
+            self.advance();
+            tuple = true;
+        }
+        self.consume(
+            TokenType::CloseParen,
+            &format!(
+                "Expected ')' after tuple pattern, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        if tuple || elements.is_empty() {
+            Ok(Pattern::Tuple(elements))
+        } else {
+            Ok(elements.remove(0))
+        }
+    }
+
+    fn array_pattern(&mut self) -> Result<Pattern, Error> {
+        self.advance();
+        let mut head = Vec::new();
+        let mut rest = None;
+        let mut tail = Vec::new();
+        while !self.does_match(&[TokenType::CloseBracket]) {
+            let line = self.peek().line;
+            let (pattern, is_rest) = if self.does_match(&[TokenType::DotDot]) {
+                self.advance();
+                (Pattern::Wildcard, true)
+            } else {
+                let pattern = self.pattern()?;
+                let is_rest = matches!(pattern, Pattern::Wildcard | Pattern::Binding(_))
+                    && self.does_match(&[TokenType::DotDot]);
+                if is_rest {
+                    self.advance();
+                }
+                (pattern, is_rest)
+            };
+            if is_rest {
+                if rest.is_some() {
+                    return Err(Error::new(
+                        ErrorType::ParsingError,
+                        "An array pattern can only have one rest '..'",
+                        line,
+                    ));
+                }
+                rest = Some(Box::new(pattern));
+            } else if rest.is_some() {
+                tail.push(pattern);
+            } else {
+                head.push(pattern);
+            }
+            if !self.does_match(&[TokenType::Comma]) {
+                break;
+            }
+            self.advance();
+        }
+        self.consume(
+            TokenType::CloseBracket,
+            &format!(
+                "Expected ']' after array pattern, found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        Ok(Pattern::Array { head, rest, tail })
+    }
+
+    fn variant_pattern(&mut self, enumeration: Token) -> Result<Pattern, Error> {
+        self.advance();
+        let variant = self.consume(
+            TokenType::Identifier,
+            &format!(
+                "Expected variant name after '::', found '{}'",
+                self.peek().lexeme
+            ),
+        )?;
+        let mut fields = None;
+        if self.does_match(&[TokenType::OpenParen]) {
+            self.advance();
+            let mut patterns = Vec::new();
+            while !self.does_match(&[TokenType::CloseParen]) {
+                patterns.push(self.pattern()?);
+                if !self.does_match(&[TokenType::Comma]) {
+                    break;
+                }
+                self.advance();
+            }
+            self.consume(
+                TokenType::CloseParen,
+                &format!(
+                    "Expected ')' after variant patterns, found '{}'",
+                    self.peek().lexeme
+                ),
+            )?;
+            fields = Some(patterns);
+        }
+        Ok(Pattern::Variant {
+            enumeration,
+            variant,
+            fields,
+        })
+    }
+
+    fn pattern_literal(&mut self) -> Result<Object, Error> {
+        if self.does_match(&[TokenType::Minus]) {
+            self.advance();
+            return match self.peek().literal {
+                Object::Integer(integer) if self.does_match(&[TokenType::Number]) => {
+                    self.advance();
+                    Ok(Object::Integer(-integer))
+                }
+                Object::Float(float) if self.does_match(&[TokenType::Number]) => {
+                    self.advance();
+                    Ok(Object::Float(-float))
+                }
+                _ => Err(self.error(&format!(
+                    "Expected number after '-' in pattern, found '{}'",
+                    self.peek().lexeme
+                ))),
+            };
+        }
+        if self.does_match(&[
+            TokenType::Number,
+            TokenType::String,
+            TokenType::True,
+            TokenType::False,
+            TokenType::Nil,
+        ]) {
+            return Ok(self.next_token().literal);
+        }
+        Err(self.error(&format!("Expected pattern, found '{}'", self.peek().lexeme)))
+    }
+
+    fn warn(&mut self, message: &str, line: usize) {
+        self.warnings.push(Warning::new(message, line));
+    }
+
     fn is_eof(&self) -> bool {
         self.tokens[self.current].ttype == TokenType::EOF
     }
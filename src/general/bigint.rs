@@ -0,0 +1,649 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter, Result},
+    ops::{Add, BitAnd, BitOr, BitXor, Mul, Neg, Not, Rem, Shl, Shr, Sub},
+    str::FromStr,
+};
+
+const LIMB_BITS: u32 = 32;
+const LIMB_BASE: f64 = 4_294_967_296.;
+
+// An arbitrary precision integer, stored as its sign and the magnitude in
+// base 2^32 limbs, least significant first. Magnitudes never carry leading
+// zero limbs and zero is never negative, so the derived equality and hash
+// are correct.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    fn new(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        Self {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0_u64, |value, limb| (value << LIMB_BITS) | *limb as u64);
+        if self.negative {
+            0_i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0., |value, limb| value * LIMB_BASE + *limb as f64);
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    // Truncates towards zero, like converting a float to an integer does.
+    pub fn from_f64(float: f64) -> Option<Self> {
+        if !float.is_finite() {
+            return None;
+        }
+        let mut rest = float.abs().trunc();
+        let mut magnitude = Vec::new();
+        while rest >= 1. {
+            magnitude.push((rest % LIMB_BASE) as u32);
+            rest = (rest / LIMB_BASE).trunc();
+        }
+        Some(Self::new(float < 0., magnitude))
+    }
+
+    // Parses unsigned digits in the given radix.
+    pub fn from_str_radix(digits: &str, radix: u32) -> Option<Self> {
+        if digits.is_empty() {
+            return None;
+        }
+        let mut magnitude = Vec::new();
+        for digit in digits.chars() {
+            let digit = digit.to_digit(radix)?;
+            magnitude = multiply_small(&magnitude, radix, digit);
+        }
+        Some(Self::new(false, magnitude))
+    }
+
+    // Formats the digits in the given radix, which must be at most 36.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let mut digits = Vec::new();
+        let mut rest = self.magnitude.clone();
+        while !rest.is_empty() {
+            let (quotient, remainder) = divide_small(&rest, radix);
+            digits.push(char::from_digit(remainder, radix).unwrap());
+            rest = quotient;
+        }
+        if digits.is_empty() {
+            digits.push('0');
+        }
+        if self.negative {
+            digits.push('-');
+        }
+        digits.iter().rev().collect()
+    }
+
+    // The lowest `length` limbs of the two's complement representation.
+    fn twos_complement(&self, length: usize) -> Vec<u32> {
+        let mut limbs: Vec<u32> = (0..length)
+            .map(|index| *self.magnitude.get(index).unwrap_or(&0))
+            .collect();
+        if self.negative {
+            negate(&mut limbs);
+        }
+        limbs
+    }
+
+    fn from_twos_complement(mut limbs: Vec<u32>) -> Self {
+        let negative = limbs
+            .last()
+            .is_some_and(|limb| limb >> (LIMB_BITS - 1) == 1);
+        if negative {
+            negate(&mut limbs);
+        }
+        Self::new(negative, limbs)
+    }
+
+    // Applies a bitwise operator as if both numbers were stored in two's
+    // complement with infinitely many sign bits, like i64 but unbounded.
+    fn bitwise(&self, other: &Self, operator: fn(u32, u32) -> u32) -> Self {
+        // One extra limb always leaves room for the sign bit.
+        let length = self.magnitude.len().max(other.magnitude.len()) + 1;
+        let limbs = self
+            .twos_complement(length)
+            .into_iter()
+            .zip(other.twos_complement(length))
+            .map(|(x, y)| operator(x, y))
+            .collect();
+        Self::from_twos_complement(limbs)
+    }
+
+    // Truncating division, so the remainder takes the sign of `self` just
+    // like `%` on i64. Gives None when dividing by zero.
+    pub fn div_rem(&self, other: &Self) -> Option<(Self, Self)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = divide(&self.magnitude, &other.magnitude);
+        Some((
+            Self::new(self.negative != other.negative, quotient),
+            Self::new(self.negative, remainder),
+        ))
+    }
+
+    // Division rounding towards negative infinity, so `-7 ~/ 2` is -4.
+    // Gives None when dividing by zero.
+    pub fn div_floor(&self, other: &Self) -> Option<Self> {
+        let (quotient, remainder) = self.div_rem(other)?;
+        if !remainder.is_zero() && self.negative != other.negative {
+            Some(&quotient - &Self::from(1))
+        } else {
+            Some(quotient)
+        }
+    }
+
+    // Exponentiation by squaring.
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = Self::from(1);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(integer: i64) -> Self {
+        let magnitude = integer.unsigned_abs();
+        Self::new(
+            integer < 0,
+            vec![magnitude as u32, (magnitude >> LIMB_BITS) as u32],
+        )
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ();
+
+    fn from_str(string: &str) -> std::result::Result<Self, Self::Err> {
+        let (negative, digits) = match string.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, string.strip_prefix('+').unwrap_or(string)),
+        };
+        let magnitude = Self::from_str_radix(digits, 10).ok_or(())?;
+        Ok(Self::new(negative, magnitude.magnitude))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare(&self.magnitude, &other.magnitude),
+            (true, true) => compare(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, add(&self.magnitude, &other.magnitude));
+        }
+        match compare(&self.magnitude, &other.magnitude) {
+            Ordering::Less => {
+                BigInt::new(other.negative, subtract(&other.magnitude, &self.magnitude))
+            }
+            _ => BigInt::new(self.negative, subtract(&self.magnitude, &other.magnitude)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::new(
+            self.negative != other.negative,
+            multiply(&self.magnitude, &other.magnitude),
+        )
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    // Panics when dividing by zero, callers check for it first.
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).expect("division by zero").1
+    }
+}
+
+impl BitAnd for &BigInt {
+    type Output = BigInt;
+
+    fn bitand(self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |x, y| x & y)
+    }
+}
+
+impl BitOr for &BigInt {
+    type Output = BigInt;
+
+    fn bitor(self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |x, y| x | y)
+    }
+}
+
+impl BitXor for &BigInt {
+    type Output = BigInt;
+
+    fn bitxor(self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |x, y| x ^ y)
+    }
+}
+
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        &-self - &BigInt::from(1)
+    }
+}
+
+impl Shl<usize> for &BigInt {
+    type Output = BigInt;
+
+    fn shl(self, amount: usize) -> BigInt {
+        BigInt::new(self.negative, shift_left(&self.magnitude, amount))
+    }
+}
+
+// Rounds towards negative infinity like an arithmetic shift on i64, so
+// `-1 >> 1` stays -1.
+impl Shr<usize> for &BigInt {
+    type Output = BigInt;
+
+    fn shr(self, amount: usize) -> BigInt {
+        if !self.negative {
+            return BigInt::new(false, shift_right(&self.magnitude, amount));
+        }
+        let one = BigInt::from(1);
+        let magnitude = &-self - &one;
+        let shifted = BigInt::new(false, shift_right(&magnitude.magnitude, amount));
+        -&(&shifted + &one)
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        const CHUNK: u32 = 1_000_000_000;
+        let mut chunks = Vec::new();
+        let mut rest = self.magnitude.clone();
+        while !rest.is_empty() {
+            let (quotient, remainder) = divide_small(&rest, CHUNK);
+            chunks.push(remainder);
+            rest = quotient;
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        match chunks.pop() {
+            Some(first) => write!(f, "{}", first)?,
+            None => write!(f, "0")?,
+        }
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+fn compare(x: &[u32], y: &[u32]) -> Ordering {
+    x.len()
+        .cmp(&y.len())
+        .then_with(|| x.iter().rev().cmp(y.iter().rev()))
+}
+
+fn add(x: &[u32], y: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(x.len().max(y.len()) + 1);
+    let mut carry = 0_u64;
+    for index in 0..x.len().max(y.len()) {
+        let limb = carry + *x.get(index).unwrap_or(&0) as u64 + *y.get(index).unwrap_or(&0) as u64;
+        sum.push(limb as u32);
+        carry = limb >> LIMB_BITS;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+// Requires x >= y.
+fn subtract(x: &[u32], y: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(x.len());
+    let mut borrow = 0_i64;
+    for (index, limb) in x.iter().enumerate() {
+        let mut limb = *limb as i64 - *y.get(index).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if limb < 0 {
+            limb += 1 << LIMB_BITS;
+            borrow = 1;
+        }
+        difference.push(limb as u32);
+    }
+    difference
+}
+
+// Turns two's complement limbs into their negation in place.
+fn negate(limbs: &mut [u32]) {
+    let mut carry = true;
+    for limb in limbs {
+        let (negated, overflow) = (!*limb).overflowing_add(carry as u32);
+        *limb = negated;
+        carry = overflow;
+    }
+}
+
+fn shift_left(x: &[u32], amount: usize) -> Vec<u32> {
+    let bits = (amount % LIMB_BITS as usize) as u32;
+    let mut shifted = vec![0_u32; amount / LIMB_BITS as usize];
+    let mut carry = 0_u32;
+    for limb in x {
+        if bits == 0 {
+            shifted.push(*limb);
+        } else {
+            shifted.push((limb << bits) | carry);
+            carry = limb >> (LIMB_BITS - bits);
+        }
+    }
+    shifted.push(carry);
+    shifted
+}
+
+fn shift_right(x: &[u32], amount: usize) -> Vec<u32> {
+    let bits = (amount % LIMB_BITS as usize) as u32;
+    let limbs = amount / LIMB_BITS as usize;
+    (limbs..x.len())
+        .map(|index| {
+            let high = match x.get(index + 1) {
+                Some(next) if bits > 0 => next << (LIMB_BITS - bits),
+                _ => 0,
+            };
+            (x[index] >> bits) | high
+        })
+        .collect()
+}
+
+fn multiply(x: &[u32], y: &[u32]) -> Vec<u32> {
+    let mut product = vec![0_u32; x.len() + y.len()];
+    for (i, a) in x.iter().enumerate() {
+        let mut carry = 0_u64;
+        for (j, b) in y.iter().enumerate() {
+            let limb = product[i + j] as u64 + *a as u64 * *b as u64 + carry;
+            product[i + j] = limb as u32;
+            carry = limb >> LIMB_BITS;
+        }
+        product[i + y.len()] = carry as u32;
+    }
+    product
+}
+
+fn multiply_small(x: &[u32], factor: u32, addend: u32) -> Vec<u32> {
+    let mut product = Vec::with_capacity(x.len() + 1);
+    let mut carry = addend as u64;
+    for limb in x {
+        let limb = *limb as u64 * factor as u64 + carry;
+        product.push(limb as u32);
+        carry = limb >> LIMB_BITS;
+    }
+    if carry > 0 {
+        product.push(carry as u32);
+    }
+    product
+}
+
+fn divide_small(x: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0_u32; x.len()];
+    let mut remainder = 0_u64;
+    for (index, limb) in x.iter().enumerate().rev() {
+        let value = (remainder << LIMB_BITS) | *limb as u64;
+        quotient[index] = (value / divisor as u64) as u32;
+        remainder = value % divisor as u64;
+    }
+    while quotient.last() == Some(&0) {
+        quotient.pop();
+    }
+    (quotient, remainder as u32)
+}
+
+// Schoolbook binary long division, which is plenty fast for the size of
+// numbers scripts work with.
+fn divide(x: &[u32], y: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if let [divisor] = y {
+        let (quotient, remainder) = divide_small(x, *divisor);
+        return (quotient, vec![remainder]);
+    }
+    let mut quotient = vec![0_u32; x.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+    for index in (0..x.len() * LIMB_BITS as usize).rev() {
+        remainder = multiply_small(&remainder, 2, (x[index / 32] >> (index % 32)) & 1);
+        if compare(&remainder, y) != Ordering::Less {
+            remainder = subtract(&remainder, y);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+            quotient[index / 32] |= 1 << (index % 32);
+        }
+    }
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(digits: &str) -> BigInt {
+        digits.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_prints() {
+        assert_eq!(big("0").to_string(), "0");
+        assert_eq!(big("-0").to_string(), "0");
+        assert_eq!(
+            big("-123456789012345678901234567890").to_string(),
+            "-123456789012345678901234567890"
+        );
+        assert_eq!(big("1000000000").to_string(), "1000000000");
+        assert_eq!(big("-255").to_str_radix(16), "-ff");
+        assert_eq!(BigInt::from_str_radix("ff", 16), Some(BigInt::from(255)));
+        assert_eq!(BigInt::from_str_radix("", 10), None);
+        assert_eq!(BigInt::from_str_radix("12", 2), None);
+        assert!("1-2".parse::<BigInt>().is_err());
+    }
+
+    #[test]
+    fn converts_to_i64_at_the_boundaries() {
+        assert_eq!(BigInt::from(i64::MAX).to_i64(), Some(i64::MAX));
+        assert_eq!(BigInt::from(i64::MIN).to_i64(), Some(i64::MIN));
+        assert_eq!((&BigInt::from(i64::MAX) + &BigInt::from(1)).to_i64(), None);
+        assert_eq!((&BigInt::from(i64::MIN) - &BigInt::from(1)).to_i64(), None);
+        assert_eq!(big("18446744073709551616").to_i64(), None);
+        assert_eq!(BigInt::from(0).to_i64(), Some(0));
+    }
+
+    #[test]
+    fn converts_floats() {
+        assert_eq!(BigInt::from_f64(-2.9), Some(BigInt::from(-2)));
+        assert_eq!(BigInt::from_f64(-0.5), Some(BigInt::from(0)));
+        assert_eq!(BigInt::from_f64(f64::NAN), None);
+        assert_eq!(BigInt::from_f64(f64::INFINITY), None);
+        assert_eq!(BigInt::from_f64(1e20), Some(big("100000000000000000000")));
+        assert_eq!(big("-100000000000000000000").to_f64(), -1e20);
+    }
+
+    #[test]
+    fn adds_and_subtracts_across_signs() {
+        let max = BigInt::from(i64::MAX);
+        assert_eq!((&max + &max).to_string(), "18446744073709551614");
+        assert_eq!(&big("5") + &big("-8"), BigInt::from(-3));
+        assert_eq!(&big("-5") + &big("8"), BigInt::from(3));
+        assert_eq!(&big("-5") - &big("-5"), BigInt::from(0));
+        assert!(!(&big("-5") - &big("-5")).negative);
+        assert_eq!(
+            &big("18446744073709551616") - &BigInt::from(1),
+            big("18446744073709551615")
+        );
+        assert_eq!(-&BigInt::from(i64::MIN), big("9223372036854775808"));
+    }
+
+    #[test]
+    fn multiplies() {
+        let min = BigInt::from(i64::MIN);
+        assert_eq!(
+            (&min * &min).to_string(),
+            "85070591730234615865843651857942052864"
+        );
+        assert_eq!(&min * &BigInt::from(-1), big("9223372036854775808"));
+        assert_eq!(&big("-3") * &big("0"), BigInt::from(0));
+        assert!(!(&big("-3") * &big("0")).negative);
+    }
+
+    #[test]
+    fn divides_truncating_and_flooring() {
+        let (quotient, remainder) = BigInt::from(-7).div_rem(&BigInt::from(2)).unwrap();
+        assert_eq!((quotient, remainder), (BigInt::from(-3), BigInt::from(-1)));
+        let (quotient, remainder) = BigInt::from(7).div_rem(&BigInt::from(-2)).unwrap();
+        assert_eq!((quotient, remainder), (BigInt::from(-3), BigInt::from(1)));
+        assert_eq!(
+            BigInt::from(-7).div_floor(&BigInt::from(2)),
+            Some(BigInt::from(-4))
+        );
+        assert_eq!(
+            BigInt::from(7).div_floor(&BigInt::from(-2)),
+            Some(BigInt::from(-4))
+        );
+        assert_eq!(
+            BigInt::from(-6).div_floor(&BigInt::from(2)),
+            Some(BigInt::from(-3))
+        );
+        assert_eq!(BigInt::from(1).div_rem(&BigInt::from(0)), None);
+        assert_eq!(BigInt::from(1).div_floor(&BigInt::from(0)), None);
+        assert_eq!(
+            BigInt::from(i64::MIN).div_floor(&BigInt::from(-1)),
+            Some(big("9223372036854775808"))
+        );
+        let x = big("123456789012345678901234567890");
+        let y = big("-9876543210987");
+        let (quotient, remainder) = x.div_rem(&y).unwrap();
+        assert_eq!(&(&quotient * &y) + &remainder, x);
+        assert_eq!(&x % &y, remainder);
+    }
+
+    #[test]
+    fn raises_to_powers() {
+        assert_eq!(BigInt::from(2).pow(64), big("18446744073709551616"));
+        assert_eq!(BigInt::from(-2).pow(63), BigInt::from(i64::MIN));
+        assert_eq!(BigInt::from(-3).pow(3), BigInt::from(-27));
+        assert_eq!(BigInt::from(0).pow(0), BigInt::from(1));
+        assert_eq!(BigInt::from(0).pow(5), BigInt::from(0));
+        assert_eq!(
+            BigInt::from(10).pow(30).to_string(),
+            format!("1{}", "0".repeat(30))
+        );
+    }
+
+    #[test]
+    fn shifts_like_i64() {
+        assert_eq!(&BigInt::from(1) << 64, big("18446744073709551616"));
+        assert_eq!(&BigInt::from(-1) << 64, big("-18446744073709551616"));
+        assert_eq!(&big("18446744073709551616") >> 64, BigInt::from(1));
+        assert_eq!(&BigInt::from(-1) >> 1, BigInt::from(-1));
+        assert_eq!(&BigInt::from(-7) >> 1, BigInt::from(-7 >> 1));
+        assert_eq!(&big("-18446744073709551617") >> 64, BigInt::from(-2));
+        assert_eq!(&BigInt::from(5) >> 100, BigInt::from(0));
+    }
+
+    #[test]
+    fn applies_bitwise_operators_in_twos_complement() {
+        for (x, y) in [
+            (12_i64, 10_i64),
+            (-12, 10),
+            (12, -10),
+            (-12, -10),
+            (i64::MIN, -1),
+        ] {
+            let (a, b) = (BigInt::from(x), BigInt::from(y));
+            assert_eq!(&a & &b, BigInt::from(x & y));
+            assert_eq!(&a | &b, BigInt::from(x | y));
+            assert_eq!(&a ^ &b, BigInt::from(x ^ y));
+            assert_eq!(!&a, BigInt::from(!x));
+        }
+        let huge = big("-18446744073709551616");
+        assert_eq!(&huge & &BigInt::from(-1), huge);
+        assert_eq!(&huge | &BigInt::from(0), huge);
+    }
+
+    #[test]
+    fn orders_by_sign_and_magnitude() {
+        assert!(big("-18446744073709551616") < BigInt::from(i64::MIN));
+        assert!(BigInt::from(-1) < BigInt::from(0));
+        assert!(big("18446744073709551616") > BigInt::from(i64::MAX));
+        assert_eq!(big("-0").cmp(&BigInt::from(0)), Ordering::Equal);
+    }
+}
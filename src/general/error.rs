@@ -3,10 +3,16 @@ use std::{
     process::exit,
 };
 
+use crate::general::object::Object;
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, Copy)]
 pub enum ErrorType {
     LexingError,
     ParsingError,
     RuntimeError,
+    // Raised on purpose by the 'panic' builtin.
+    Panic,
 }
 
 impl Display for ErrorType {
@@ -15,14 +21,17 @@ impl Display for ErrorType {
             Self::LexingError => write!(f, "LexingError"),
             Self::ParsingError => write!(f, "ParsingError"),
             Self::RuntimeError => write!(f, "RuntimeError"),
+            Self::Panic => write!(f, "Panic"),
         }
     }
 }
 
+#[derive(Debug)]
 pub struct Error {
     etype: ErrorType,
     message: String,
     line: usize,
+    file: Option<String>,
 }
 
 impl Error {
@@ -31,9 +40,32 @@ impl Error {
             etype,
             message: message.to_string(),
             line,
+            file: None,
         }
     }
 
+    pub fn kind(&self) -> ErrorType {
+        self.etype
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    // Names the file the error came from, unless an inner call already did.
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file.get_or_insert_with(|| file.to_string());
+        self
+    }
+
     pub fn throw(&self) {
         eprintln!("{}", self);
         exit(1);
@@ -42,6 +74,56 @@ impl Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "[line {}] {}: {}.", self.line, self.etype, self.message)
+        write!(f, "[")?;
+        if let Some(file) = &self.file {
+            write!(f, "{}, ", file)?;
+        }
+        write!(f, "line {}] {}: {}.", self.line, self.etype, self.message)
+    }
+}
+
+// Warnings point out likely mistakes without stopping the program.
+#[derive(Clone)]
+pub struct Warning {
+    message: String,
+    line: usize,
+    file: Option<String>,
+}
+
+impl Warning {
+    pub fn new(message: &str, line: usize) -> Self {
+        Self {
+            message: message.to_string(),
+            line,
+            file: None,
+        }
+    }
+
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "[")?;
+        if let Some(file) = &self.file {
+            write!(f, "{}, ", file)?;
+        }
+        write!(f, "line {}] Warning: {}.", self.line, self.message)
+    }
+}
+
+pub enum Interrupt {
+    Error(Error),
+    Return(Object),
+    Break(Option<String>, Object),
+    Continue(Option<String>),
+}
+
+impl From<Error> for Interrupt {
+    fn from(error: Error) -> Self {
+        Self::Error(error)
     }
 }
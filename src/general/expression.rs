@@ -1,11 +1,50 @@
-use crate::general::{error::Error, object::Object, token::Token};
+use std::rc::Rc;
+
+use crate::general::{
+    error::Interrupt, format::FormatSpec, object::Object, pattern::Pattern, statement::Statement,
+    token::Token,
+};
 
 pub trait ExpressionVisitor<T> {
-    fn visit_variable_expression(&self, expression: &VariableExpression) -> Result<T, Error>;
-    fn visit_literal_expression(&self, expression: &LiteralExpression) -> Result<T, Error>;
-    fn visit_unary_expression(&self, expression: &UnaryExpression) -> Result<T, Error>;
-    fn visit_binary_expression(&self, expression: &BinaryExpression) -> Result<T, Error>;
-    fn visit_gruping_expression(&self, expression: &GroupingExpression) -> Result<T, Error>;
+    fn visit_variable_expression(
+        &mut self,
+        expression: &VariableExpression,
+    ) -> Result<T, Interrupt>;
+    fn visit_literal_expression(&mut self, expression: &LiteralExpression) -> Result<T, Interrupt>;
+    fn visit_unary_expression(&mut self, expression: &UnaryExpression) -> Result<T, Interrupt>;
+    fn visit_binary_expression(&mut self, expression: &BinaryExpression) -> Result<T, Interrupt>;
+    fn visit_logical_expression(&mut self, expression: &LogicalExpression) -> Result<T, Interrupt>;
+    fn visit_gruping_expression(&mut self, expression: &GroupingExpression)
+        -> Result<T, Interrupt>;
+    fn visit_call_expression(&mut self, expression: &CallExpression) -> Result<T, Interrupt>;
+    fn visit_lambda_expression(&mut self, expression: &LambdaExpression) -> Result<T, Interrupt>;
+    fn visit_range_expression(&mut self, expression: &RangeExpression) -> Result<T, Interrupt>;
+    fn visit_tuple_expression(&mut self, expression: &TupleExpression) -> Result<T, Interrupt>;
+    fn visit_array_expression(&mut self, expression: &ArrayExpression) -> Result<T, Interrupt>;
+    fn visit_index_expression(&mut self, expression: &IndexExpression) -> Result<T, Interrupt>;
+    fn visit_map_expression(&mut self, expression: &MapExpression) -> Result<T, Interrupt>;
+    fn visit_struct_expression(&mut self, expression: &StructExpression) -> Result<T, Interrupt>;
+    fn visit_get_expression(&mut self, expression: &GetExpression) -> Result<T, Interrupt>;
+    fn visit_assignment_expression(
+        &mut self,
+        expression: &AssignmentExpression,
+    ) -> Result<T, Interrupt>;
+    fn visit_index_set_expression(
+        &mut self,
+        expression: &IndexSetExpression,
+    ) -> Result<T, Interrupt>;
+    fn visit_set_expression(&mut self, expression: &SetExpression) -> Result<T, Interrupt>;
+    fn visit_destructure_expression(
+        &mut self,
+        expression: &DestructureExpression,
+    ) -> Result<T, Interrupt>;
+    fn visit_path_expression(&mut self, expression: &PathExpression) -> Result<T, Interrupt>;
+    fn visit_match_expression(&mut self, expression: &MatchExpression) -> Result<T, Interrupt>;
+    fn visit_block_expression(&mut self, expression: &BlockExpression) -> Result<T, Interrupt>;
+    fn visit_if_expression(&mut self, expression: &IfExpression) -> Result<T, Interrupt>;
+    fn visit_loop_expression(&mut self, expression: &LoopExpression) -> Result<T, Interrupt>;
+    fn visit_try_expression(&mut self, expression: &TryExpression) -> Result<T, Interrupt>;
+    fn visit_format_expression(&mut self, expression: &FormatExpression) -> Result<T, Interrupt>;
 }
 
 #[derive(Debug)]
@@ -14,17 +53,59 @@ pub enum Expression {
     Literal(LiteralExpression),
     Unray(UnaryExpression),
     Binary(BinaryExpression),
+    Logical(LogicalExpression),
     Grouping(GroupingExpression),
+    Call(CallExpression),
+    Lambda(LambdaExpression),
+    Range(RangeExpression),
+    Tuple(TupleExpression),
+    Array(ArrayExpression),
+    Index(IndexExpression),
+    Map(MapExpression),
+    Struct(StructExpression),
+    Get(GetExpression),
+    Assignment(AssignmentExpression),
+    IndexSet(IndexSetExpression),
+    Set(SetExpression),
+    Destructure(DestructureExpression),
+    Path(PathExpression),
+    Match(MatchExpression),
+    Block(BlockExpression),
+    If(IfExpression),
+    Loop(LoopExpression),
+    Try(TryExpression),
+    Format(FormatExpression),
 }
 
 impl Expression {
-    pub fn accept<T>(&self, visitor: &dyn ExpressionVisitor<T>) -> Result<T, Error> {
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
         match self {
             Self::Variable(expression) => expression.accept(visitor),
             Self::Literal(expression) => expression.accept(visitor),
             Self::Unray(expression) => expression.accept(visitor),
             Self::Binary(expression) => expression.accept(visitor),
+            Self::Logical(expression) => expression.accept(visitor),
             Self::Grouping(expression) => expression.accept(visitor),
+            Self::Call(expression) => expression.accept(visitor),
+            Self::Lambda(expression) => expression.accept(visitor),
+            Self::Range(expression) => expression.accept(visitor),
+            Self::Tuple(expression) => expression.accept(visitor),
+            Self::Array(expression) => expression.accept(visitor),
+            Self::Index(expression) => expression.accept(visitor),
+            Self::Map(expression) => expression.accept(visitor),
+            Self::Struct(expression) => expression.accept(visitor),
+            Self::Get(expression) => expression.accept(visitor),
+            Self::Assignment(expression) => expression.accept(visitor),
+            Self::IndexSet(expression) => expression.accept(visitor),
+            Self::Set(expression) => expression.accept(visitor),
+            Self::Destructure(expression) => expression.accept(visitor),
+            Self::Path(expression) => expression.accept(visitor),
+            Self::Match(expression) => expression.accept(visitor),
+            Self::Block(expression) => expression.accept(visitor),
+            Self::If(expression) => expression.accept(visitor),
+            Self::Loop(expression) => expression.accept(visitor),
+            Self::Try(expression) => expression.accept(visitor),
+            Self::Format(expression) => expression.accept(visitor),
         }
     }
 }
@@ -39,7 +120,7 @@ impl VariableExpression {
         Self { identifier }
     }
 
-    pub fn accept<T>(&self, visitor: &dyn ExpressionVisitor<T>) -> Result<T, Error> {
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
         visitor.visit_variable_expression(self)
     }
 }
@@ -54,7 +135,7 @@ impl LiteralExpression {
         Self { literal }
     }
 
-    pub fn accept<T>(&self, visitor: &dyn ExpressionVisitor<T>) -> Result<T, Error> {
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
         visitor.visit_literal_expression(self)
     }
 }
@@ -73,7 +154,7 @@ impl UnaryExpression {
         }
     }
 
-    pub fn accept<T>(&self, visitor: &dyn ExpressionVisitor<T>) -> Result<T, Error> {
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
         visitor.visit_unary_expression(self)
     }
 }
@@ -94,11 +175,34 @@ impl BinaryExpression {
         }
     }
 
-    pub fn accept<T>(&self, visitor: &dyn ExpressionVisitor<T>) -> Result<T, Error> {
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
         visitor.visit_binary_expression(self)
     }
 }
 
+// `&&` and `||`, kept apart from the other binary operators because the
+// right operand is only evaluated when the left one does not decide.
+#[derive(Debug)]
+pub struct LogicalExpression {
+    pub left: Box<Expression>,
+    pub operator: Token,
+    pub right: Box<Expression>,
+}
+
+impl LogicalExpression {
+    pub fn new(left: Expression, operator: Token, right: Expression) -> Self {
+        Self {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_logical_expression(self)
+    }
+}
+
 #[derive(Debug)]
 pub struct GroupingExpression {
     pub expressions: Box<Expression>,
@@ -111,7 +215,447 @@ impl GroupingExpression {
         }
     }
 
-    pub fn accept<T>(&self, visitor: &dyn ExpressionVisitor<T>) -> Result<T, Error> {
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
         visitor.visit_gruping_expression(self)
     }
 }
+
+#[derive(Debug)]
+pub struct CallExpression {
+    pub callee: Box<Expression>,
+    pub paren: Token,
+    pub arguments: Vec<Expression>,
+}
+
+impl CallExpression {
+    pub fn new(callee: Expression, paren: Token, arguments: Vec<Expression>) -> Self {
+        Self {
+            callee: Box::new(callee),
+            paren,
+            arguments,
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_call_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct LambdaExpression {
+    pub parameters: Vec<Token>,
+    pub body: Rc<Vec<Statement>>,
+}
+
+impl LambdaExpression {
+    pub fn new(parameters: Vec<Token>, body: Vec<Statement>) -> Self {
+        Self {
+            parameters,
+            body: Rc::new(body),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_lambda_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct RangeExpression {
+    pub start: Box<Expression>,
+    pub operator: Token,
+    pub end: Box<Expression>,
+}
+
+impl RangeExpression {
+    pub fn new(start: Expression, operator: Token, end: Expression) -> Self {
+        Self {
+            start: Box::new(start),
+            operator,
+            end: Box::new(end),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_range_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct TupleExpression {
+    pub elements: Vec<Expression>,
+}
+
+impl TupleExpression {
+    pub fn new(elements: Vec<Expression>) -> Self {
+        Self { elements }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_tuple_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct ArrayExpression {
+    pub elements: Vec<Expression>,
+}
+
+impl ArrayExpression {
+    pub fn new(elements: Vec<Expression>) -> Self {
+        Self { elements }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_array_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct IndexExpression {
+    pub object: Box<Expression>,
+    pub bracket: Token,
+    pub index: Box<Expression>,
+}
+
+impl IndexExpression {
+    pub fn new(object: Expression, bracket: Token, index: Expression) -> Self {
+        Self {
+            object: Box::new(object),
+            bracket,
+            index: Box::new(index),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_index_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct MapExpression {
+    pub brace: Token,
+    pub entries: Vec<(Expression, Expression)>,
+}
+
+impl MapExpression {
+    pub fn new(brace: Token, entries: Vec<(Expression, Expression)>) -> Self {
+        Self { brace, entries }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_map_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct StructExpression {
+    pub identifier: Token,
+    pub fields: Vec<(Token, Expression)>,
+}
+
+impl StructExpression {
+    pub fn new(identifier: Token, fields: Vec<(Token, Expression)>) -> Self {
+        Self { identifier, fields }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_struct_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct GetExpression {
+    pub object: Box<Expression>,
+    pub identifier: Token,
+}
+
+impl GetExpression {
+    pub fn new(object: Expression, identifier: Token) -> Self {
+        Self {
+            object: Box::new(object),
+            identifier,
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_get_expression(self)
+    }
+}
+
+// In the assignment expressions `operator` is the arithmetic or bitwise
+// operator of a compound assignment, like the '+' of `x += 1`, and None for
+// a plain `=`.
+#[derive(Debug)]
+pub struct AssignmentExpression {
+    pub identifier: Token,
+    pub operator: Option<Token>,
+    pub value: Box<Expression>,
+}
+
+impl AssignmentExpression {
+    pub fn new(identifier: Token, operator: Option<Token>, value: Expression) -> Self {
+        Self {
+            identifier,
+            operator,
+            value: Box::new(value),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_assignment_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct IndexSetExpression {
+    pub object: Box<Expression>,
+    pub bracket: Token,
+    pub index: Box<Expression>,
+    pub operator: Option<Token>,
+    pub value: Box<Expression>,
+}
+
+impl IndexSetExpression {
+    pub fn new(
+        object: Expression,
+        bracket: Token,
+        index: Expression,
+        operator: Option<Token>,
+        value: Expression,
+    ) -> Self {
+        Self {
+            object: Box::new(object),
+            bracket,
+            index: Box::new(index),
+            operator,
+            value: Box::new(value),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_index_set_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct SetExpression {
+    pub object: Box<Expression>,
+    pub identifier: Token,
+    pub operator: Option<Token>,
+    pub value: Box<Expression>,
+}
+
+impl SetExpression {
+    pub fn new(
+        object: Expression,
+        identifier: Token,
+        operator: Option<Token>,
+        value: Expression,
+    ) -> Self {
+        Self {
+            object: Box::new(object),
+            identifier,
+            operator,
+            value: Box::new(value),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_set_expression(self)
+    }
+}
+
+// `(a, b) = (b, a)` assigns every variable bound by the pattern at once.
+#[derive(Debug)]
+pub struct DestructureExpression {
+    pub pattern: Pattern,
+    pub equal: Token,
+    pub value: Box<Expression>,
+}
+
+impl DestructureExpression {
+    pub fn new(pattern: Pattern, equal: Token, value: Expression) -> Self {
+        Self {
+            pattern,
+            equal,
+            value: Box::new(value),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_destructure_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct PathExpression {
+    pub object: Box<Expression>,
+    pub identifier: Token,
+}
+
+impl PathExpression {
+    pub fn new(object: Expression, identifier: Token) -> Self {
+        Self {
+            object: Box::new(object),
+            identifier,
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_path_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expression>,
+    pub body: Expression,
+}
+
+impl MatchArm {
+    pub fn new(pattern: Pattern, guard: Option<Expression>, body: Expression) -> Self {
+        Self {
+            pattern,
+            guard,
+            body,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MatchExpression {
+    pub keyword: Token,
+    pub subject: Box<Expression>,
+    pub arms: Vec<MatchArm>,
+}
+
+impl MatchExpression {
+    pub fn new(keyword: Token, subject: Expression, arms: Vec<MatchArm>) -> Self {
+        Self {
+            keyword,
+            subject: Box::new(subject),
+            arms,
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_match_expression(self)
+    }
+}
+
+// The value of a block is its trailing expression, the one not followed by
+// a semicolon, or nil when there is none.
+#[derive(Debug)]
+pub struct BlockExpression {
+    pub statements: Vec<Statement>,
+    pub tail: Option<Box<Expression>>,
+}
+
+impl BlockExpression {
+    pub fn new(statements: Vec<Statement>, tail: Option<Expression>) -> Self {
+        Self {
+            statements,
+            tail: tail.map(Box::new),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_block_expression(self)
+    }
+}
+
+// The else branch is either another if expression or a block. Without one
+// a false condition gives nil.
+#[derive(Debug)]
+pub struct IfExpression {
+    pub condition: Box<Expression>,
+    pub then_branch: BlockExpression,
+    pub else_branch: Option<Box<Expression>>,
+}
+
+impl IfExpression {
+    pub fn new(
+        condition: Expression,
+        then_branch: BlockExpression,
+        else_branch: Option<Expression>,
+    ) -> Self {
+        Self {
+            condition: Box::new(condition),
+            then_branch,
+            else_branch: else_branch.map(Box::new),
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_if_expression(self)
+    }
+}
+
+// `loop { ... }` runs until a 'break', whose value becomes the value of
+// the loop.
+#[derive(Debug)]
+pub struct LoopExpression {
+    pub label: Option<Token>,
+    pub body: BlockExpression,
+}
+
+impl LoopExpression {
+    pub fn new(label: Option<Token>, body: BlockExpression) -> Self {
+        Self { label, body }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_loop_expression(self)
+    }
+}
+
+// `try { ... } catch error { ... }` gives the value of the try block, or of
+// the catch block when a runtime error stops the try block early.
+#[derive(Debug)]
+pub struct TryExpression {
+    pub body: BlockExpression,
+    pub binding: Option<Token>,
+    pub handler: BlockExpression,
+}
+
+impl TryExpression {
+    pub fn new(body: BlockExpression, binding: Option<Token>, handler: BlockExpression) -> Self {
+        Self {
+            body,
+            binding,
+            handler,
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_try_expression(self)
+    }
+}
+
+#[derive(Debug)]
+pub enum FormatPart {
+    Literal(String),
+    // The closing brace token gives the line for formatting errors.
+    Interpolation {
+        expression: Box<Expression>,
+        spec: FormatSpec,
+        brace: Token,
+    },
+}
+
+#[derive(Debug)]
+pub struct FormatExpression {
+    pub parts: Vec<FormatPart>,
+}
+
+impl FormatExpression {
+    pub fn new(parts: Vec<FormatPart>) -> Self {
+        Self { parts }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn ExpressionVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_format_expression(self)
+    }
+}
@@ -0,0 +1,222 @@
+use crate::general::object::Object;
+
+#[derive(Debug, Clone, Copy)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Display,
+    Debug,
+    Binary,
+    Octal,
+    LowerHex,
+    UpperHex,
+    LowerExp,
+    UpperExp,
+}
+
+// The part after the colon in `f"{value:>8.2}"`, following Rust's
+// `[[fill]align][sign]['#']['0'][width]['.' precision][type]` grammar.
+#[derive(Debug)]
+pub struct FormatSpec {
+    pub text: String,
+    fill: char,
+    align: Option<Align>,
+    sign: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    kind: Kind,
+}
+
+impl FormatSpec {
+    // Gives None when the spec is malformed.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut spec = Self {
+            text: text.to_string(),
+            fill: ' ',
+            align: None,
+            sign: false,
+            alternate: false,
+            zero: false,
+            width: 0,
+            precision: None,
+            kind: Kind::Display,
+        };
+        let mut rest = text;
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next().and_then(align)) {
+            (Some(fill), Some(alignment)) => {
+                spec.fill = fill;
+                spec.align = Some(alignment);
+                rest = chars.as_str();
+            }
+            (Some(first), _) if align(first).is_some() => {
+                spec.align = align(first);
+                rest = &rest[1..];
+            }
+            _ => {}
+        }
+        if let Some(after) = rest.strip_prefix('+') {
+            spec.sign = true;
+            rest = after;
+        }
+        if let Some(after) = rest.strip_prefix('#') {
+            spec.alternate = true;
+            rest = after;
+        }
+        if let Some(after) = rest.strip_prefix('0') {
+            spec.zero = true;
+            rest = after;
+        }
+        let (width, after) = number(rest);
+        if width.is_none() && after.len() < rest.len() {
+            return None;
+        }
+        spec.width = width.unwrap_or(0);
+        rest = after;
+        if let Some(after) = rest.strip_prefix('.') {
+            let (precision, after) = number(after);
+            spec.precision = Some(precision?);
+            rest = after;
+        }
+        spec.kind = match rest {
+            "" => Kind::Display,
+            "?" => Kind::Debug,
+            "b" => Kind::Binary,
+            "o" => Kind::Octal,
+            "x" => Kind::LowerHex,
+            "X" => Kind::UpperHex,
+            "e" => Kind::LowerExp,
+            "E" => Kind::UpperExp,
+            _ => return None,
+        };
+        Some(spec)
+    }
+
+    pub fn format(&self, value: &Object) -> Result<String, String> {
+        let numeric = value.as_float().is_some();
+        let (negative, prefix, body) = match self.kind {
+            // Like in Rust, the precision is ignored for integers, so that
+            // big ones keep all of their digits.
+            Kind::Display | Kind::Debug if numeric => match (value, self.precision) {
+                (Object::Float(float), Some(precision)) => (
+                    float.is_sign_negative(),
+                    "",
+                    format!("{:.*}", precision, float.abs()),
+                ),
+                _ => {
+                    let text = value.to_string();
+                    match text.strip_prefix('-') {
+                        Some(magnitude) => (true, "", magnitude.to_string()),
+                        None => (false, "", text),
+                    }
+                }
+            },
+            Kind::Display => {
+                let text = value.to_string();
+                match (value, self.precision) {
+                    (Object::String(_), Some(precision)) => {
+                        (false, "", text.chars().take(precision).collect())
+                    }
+                    _ => (false, "", text),
+                }
+            }
+            Kind::Debug => match value {
+                Object::String(string) => (false, "", format!("{:?}", string)),
+                _ => (false, "", value.to_string()),
+            },
+            Kind::Binary | Kind::Octal | Kind::LowerHex | Kind::UpperHex => {
+                let integer = value.as_big_int().ok_or_else(|| {
+                    format!(
+                        "Can not format '{}' with ':{}', expected an integer",
+                        value, self.text
+                    )
+                })?;
+                let (radix, prefix) = match self.kind {
+                    Kind::Binary => (2, "0b"),
+                    Kind::Octal => (8, "0o"),
+                    _ => (16, "0x"),
+                };
+                let mut digits = integer.to_str_radix(radix);
+                if matches!(self.kind, Kind::UpperHex) {
+                    digits = digits.to_uppercase();
+                }
+                let prefix = if self.alternate { prefix } else { "" };
+                match digits.strip_prefix('-') {
+                    Some(magnitude) => (true, prefix, magnitude.to_string()),
+                    None => (false, prefix, digits),
+                }
+            }
+            Kind::LowerExp | Kind::UpperExp => {
+                let float = value.as_float().ok_or_else(|| {
+                    format!(
+                        "Can not format '{}' with ':{}', expected a number",
+                        value, self.text
+                    )
+                })?;
+                let mut body = match self.precision {
+                    Some(precision) => format!("{:.*e}", precision, float.abs()),
+                    None => format!("{:e}", float.abs()),
+                };
+                if matches!(self.kind, Kind::UpperExp) {
+                    body = body.to_uppercase();
+                }
+                (float.is_sign_negative(), "", body)
+            }
+        };
+        let sign = match (negative, self.sign && numeric) {
+            (true, _) => "-",
+            (false, true) => "+",
+            (false, false) => "",
+        };
+        let length = sign.len() + prefix.len() + body.chars().count();
+        let padding = self.width.saturating_sub(length);
+        if self.zero && numeric {
+            return Ok(format!("{}{}{}{}", sign, prefix, "0".repeat(padding), body));
+        }
+        let (before, after) = match self.align {
+            Some(Align::Left) => (0, padding),
+            Some(Align::Center) => (padding / 2, padding - padding / 2),
+            Some(Align::Right) => (padding, 0),
+            None if numeric => (padding, 0),
+            None => (0, padding),
+        };
+        let fill = |count| self.fill.to_string().repeat(count);
+        Ok(format!(
+            "{}{}{}{}{}",
+            fill(before),
+            sign,
+            prefix,
+            body,
+            fill(after)
+        ))
+    }
+}
+
+fn align(character: char) -> Option<Align> {
+    match character {
+        '<' => Some(Align::Left),
+        '^' => Some(Align::Center),
+        '>' => Some(Align::Right),
+        _ => None,
+    }
+}
+
+// Splits leading digits off `text`, giving None for the number when there
+// are none or when they are above u16::MAX, which Rust's formatting
+// machinery does not support for widths and precisions.
+fn number(text: &str) -> (Option<usize>, &str) {
+    let end = text
+        .find(|character: char| !character.is_ascii_digit())
+        .unwrap_or(text.len());
+    (
+        text[..end].parse::<u16>().ok().map(usize::from),
+        &text[end..],
+    )
+}
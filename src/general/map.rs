@@ -0,0 +1,137 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result},
+};
+
+use std::rc::Rc;
+
+use crate::general::{bigint::BigInt, object::Object};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    Integer(i64),
+    BigInt(BigInt),
+    // Floats are stored by their bits, since f64 itself is not hashable.
+    Float(u64),
+    Boolean(bool),
+    String(String),
+}
+
+impl MapKey {
+    // Only strings, booleans and (non NaN) numbers can be used as keys, since
+    // every other object is either mutable or has no meaningful equality.
+    // Whole floats share their key with the equal integer, as `1 == 1.0`.
+    pub fn from_object(object: &Object) -> Option<Self> {
+        match object {
+            Object::Integer(integer) => Some(Self::Integer(*integer)),
+            Object::BigInt(integer) => Some(Self::BigInt(integer.as_ref().clone())),
+            Object::Float(float) if float.is_nan() => None,
+            Object::Float(float)
+                if float.fract() == 0. && *float >= i64::MIN as f64 && *float < i64::MAX as f64 =>
+            {
+                Some(Self::Integer(*float as i64))
+            }
+            Object::Float(float) => Some(Self::Float(float.to_bits())),
+            Object::Boolean(boolean) => Some(Self::Boolean(*boolean)),
+            Object::String(string) => Some(Self::String(string.clone())),
+            _ => None,
+        }
+    }
+
+    pub fn to_object(&self) -> Object {
+        match self {
+            Self::Integer(integer) => Object::Integer(*integer),
+            Self::BigInt(integer) => Object::BigInt(Rc::new(integer.clone())),
+            Self::Float(bits) => Object::Float(f64::from_bits(*bits)),
+            Self::Boolean(boolean) => Object::Boolean(*boolean),
+            Self::String(string) => Object::String(string.clone()),
+        }
+    }
+}
+
+impl Display for MapKey {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::String(string) => write!(f, "{:?}", string),
+            _ => write!(f, "{}", self.to_object()),
+        }
+    }
+}
+
+// A hash map that remembers insertion order, so that printing and iterating
+// over a map is deterministic.
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+    entries: Vec<(MapKey, Object)>,
+    indices: HashMap<MapKey, usize>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn get(&self, key: &MapKey) -> Option<&Object> {
+        self.indices.get(key).map(|index| &self.entries[*index].1)
+    }
+
+    pub fn contains_key(&self, key: &MapKey) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    pub fn insert(&mut self, key: MapKey, value: Object) {
+        if let Some(index) = self.indices.get(&key) {
+            self.entries[*index].1 = value;
+        } else {
+            self.indices.insert(key.clone(), self.entries.len());
+            self.entries.push((key, value));
+        }
+    }
+
+    pub fn remove(&mut self, key: &MapKey) -> Option<Object> {
+        let index = self.indices.remove(key)?;
+        let (_, value) = self.entries.remove(index);
+        for (_, position) in self.indices.iter_mut() {
+            if *position > index {
+                *position -= 1;
+            }
+        }
+        Some(value)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &MapKey> {
+        self.entries.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Object> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .entries
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{{")?;
+        for (index, (key, value)) in self.entries.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: ", key)?;
+            value.fmt_nested(f)?;
+        }
+        write!(f, "}}")
+    }
+}
@@ -1,5 +1,9 @@
+pub mod bigint;
 pub mod error;
 pub mod expression;
+pub mod format;
+pub mod map;
 pub mod object;
+pub mod pattern;
 pub mod statement;
 pub mod token;
@@ -1,30 +1,262 @@
-use std::fmt::{Display, Formatter, Result};
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt::{Display, Formatter, Result},
+    rc::Rc,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+use crate::{
+    general::{bigint::BigInt, error::Error, map::Map},
+    runtime::{
+        callable::{BoundMethod, Closure, Function, NativeFunction, VariantConstructor},
+        instance::{EnumDefinition, EnumValue, Instance, StructDefinition},
+        module::Module,
+    },
+};
+
+#[derive(Debug, Clone)]
 pub enum Object {
-    Number(f64),
+    Integer(i64),
+    // Integers that do not fit into an i64. Arithmetic promotes to this on
+    // overflow and demotes back as soon as the result fits again.
+    BigInt(Rc<BigInt>),
+    Float(f64),
     Boolean(bool),
     String(String),
+    // Tuples are immutable, so sharing them is never observable.
+    Tuple(Rc<Vec<Object>>),
+    // Arrays are shared by reference: assigning or passing one around never
+    // copies it, so a mutation through any handle is seen by all of them.
+    Array(Rc<RefCell<Vec<Object>>>),
+    // Maps are shared by reference as well, and keep their insertion order.
+    Map(Rc<RefCell<Map>>),
+    Struct(Rc<StructDefinition>),
+    // Struct instances are shared by reference, just like arrays and maps.
+    Instance(Rc<RefCell<Instance>>),
+    Enum(Rc<EnumDefinition>),
+    // Enum values are immutable, so unlike instances they need no RefCell.
+    EnumValue(Rc<EnumValue>),
+    Function(Rc<Function>),
+    Closure(Rc<Closure>),
+    NativeFunction(Rc<NativeFunction>),
+    BoundMethod(Rc<BoundMethod>),
+    VariantConstructor(Rc<VariantConstructor>),
+    Module(Rc<Module>),
+    // An error caught by 'try', exposing its message, kind and line.
+    Error(Rc<Error>),
+    Range {
+        start: i64,
+        end: i64,
+        inclusive: bool,
+    },
     Nil,
 }
 
 impl Display for Object {
     fn fmt(&self, f: &mut Formatter) -> Result {
         match self {
-            Self::Number(number) => write!(f, "{}", number),
+            Self::Integer(integer) => write!(f, "{}", integer),
+            Self::BigInt(integer) => write!(f, "{}", integer),
+            // Debug formatting keeps the fraction of whole floats, so `1.0`
+            // never prints like the integer `1`.
+            Self::Float(float) => write!(f, "{:?}", float),
             Self::Boolean(boolean) => write!(f, "{}", boolean),
             Self::String(string) => write!(f, "{}", string),
+            Self::Tuple(elements) => {
+                write!(f, "(")?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element.fmt_nested(f)?;
+                }
+                // A single element tuple keeps its trailing comma, so that
+                // it does not print like a parenthesized value.
+                if elements.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Self::Array(array) => fmt_once(array, f, "[...]", |f| {
+                write!(f, "[")?;
+                for (index, element) in array.borrow().iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element.fmt_nested(f)?;
+                }
+                write!(f, "]")
+            }),
+            Self::Map(map) => fmt_once(map, f, "{...}", |f| write!(f, "{}", map.borrow())),
+            Self::Struct(definition) => write!(f, "{}", definition),
+            Self::Instance(instance) => {
+                let placeholder = format!(
+                    "{} {{ ... }}",
+                    instance.borrow().definition.identifier.lexeme
+                );
+                fmt_once(instance, f, &placeholder, |f| {
+                    write!(f, "{}", instance.borrow())
+                })
+            }
+            Self::Enum(definition) => write!(f, "{}", definition),
+            Self::EnumValue(value) => write!(f, "{}", value),
+            Self::Function(function) => write!(f, "{}", function),
+            Self::Closure(closure) => write!(f, "{}", closure),
+            Self::NativeFunction(function) => write!(f, "{}", function),
+            Self::BoundMethod(method) => write!(f, "{}", method),
+            Self::VariantConstructor(constructor) => write!(f, "{}", constructor),
+            Self::Module(module) => write!(f, "{}", module),
+            Self::Error(error) => write!(f, "{}", error),
+            Self::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                if *inclusive {
+                    write!(f, "{}..={}", start, end)
+                } else {
+                    write!(f, "{}..{}", start, end)
+                }
+            }
             Self::Nil => write!(f, "nil"),
         }
     }
 }
 
+thread_local! {
+    // The collections that are being printed right now, so that one that
+    // contains itself prints as `[...]` instead of recursing forever.
+    static PRINTING: RefCell<Vec<*const ()>> = const { RefCell::new(Vec::new()) };
+    // Likewise the pairs of collections that are being compared right now.
+    // Meeting a pair again means both sides recurse the same way, so the
+    // comparison is decided by the rest of their elements.
+    static COMPARING: RefCell<Vec<(*const (), *const ())>> = const { RefCell::new(Vec::new()) };
+}
+
+fn fmt_once<T>(
+    value: &Rc<RefCell<T>>,
+    f: &mut Formatter,
+    placeholder: &str,
+    fmt: impl FnOnce(&mut Formatter) -> Result,
+) -> Result {
+    let pointer = Rc::as_ptr(value) as *const ();
+    if PRINTING.with(|printing| printing.borrow().contains(&pointer)) {
+        return write!(f, "{}", placeholder);
+    }
+    PRINTING.with(|printing| printing.borrow_mut().push(pointer));
+    let result = fmt(f);
+    PRINTING.with(|printing| printing.borrow_mut().pop());
+    result
+}
+
+fn eq_once<T: PartialEq>(x: &Rc<RefCell<T>>, y: &Rc<RefCell<T>>) -> bool {
+    if Rc::ptr_eq(x, y) {
+        return true;
+    }
+    let pair = (Rc::as_ptr(x) as *const (), Rc::as_ptr(y) as *const ());
+    if COMPARING.with(|comparing| comparing.borrow().contains(&pair)) {
+        return true;
+    }
+    COMPARING.with(|comparing| comparing.borrow_mut().push(pair));
+    let result = x == y;
+    COMPARING.with(|comparing| comparing.borrow_mut().pop());
+    result
+}
+
 impl Object {
+    // Strings nested inside collections are quoted so that `["1", 1]` does
+    // not print the same as `[1, 1]`.
+    pub fn fmt_nested(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::String(string) => write!(f, "{:?}", string),
+            _ => write!(f, "{}", self),
+        }
+    }
+
+    pub fn integer(value: BigInt) -> Self {
+        match value.to_i64() {
+            Some(integer) => Self::Integer(integer),
+            None => Self::BigInt(Rc::new(value)),
+        }
+    }
+
+    pub fn as_big_int(&self) -> Option<BigInt> {
+        match self {
+            Self::Integer(integer) => Some(BigInt::from(*integer)),
+            Self::BigInt(integer) => Some(integer.as_ref().clone()),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Integer(integer) => Some(*integer as f64),
+            Self::BigInt(integer) => Some(integer.to_f64()),
+            Self::Float(float) => Some(*float),
+            _ => None,
+        }
+    }
+
+    // Orders two numbers of either type, and gives None for anything else.
+    pub fn compare_numbers(&self, other: &Object) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(x), Self::Integer(y)) => Some(x.cmp(y)),
+            (Self::Integer(_) | Self::BigInt(_), Self::Integer(_) | Self::BigInt(_)) => {
+                Some(self.as_big_int()?.cmp(&other.as_big_int()?))
+            }
+            _ => self.as_float()?.partial_cmp(&other.as_float()?),
+        }
+    }
+
     pub fn is_truthy(&self) -> bool {
         match self {
             Self::Nil => false,
-            Self::Boolean(x) => x.clone(),
+            Self::Boolean(x) => *x,
             _ => true,
         }
     }
 }
+
+// Integers and floats compare by value, so `1 == 1.0`, everything else only
+// equals objects of the same kind.
+impl PartialEq for Object {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Integer(x), Self::Integer(y)) => x == y,
+            (
+                Self::Integer(_) | Self::BigInt(_) | Self::Float(_),
+                Self::Integer(_) | Self::BigInt(_) | Self::Float(_),
+            ) => self.compare_numbers(other) == Some(Ordering::Equal),
+            (Self::Boolean(x), Self::Boolean(y)) => x == y,
+            (Self::String(x), Self::String(y)) => x == y,
+            (Self::Tuple(x), Self::Tuple(y)) => x == y,
+            (Self::Array(x), Self::Array(y)) => eq_once(x, y),
+            (Self::Map(x), Self::Map(y)) => eq_once(x, y),
+            (Self::Struct(x), Self::Struct(y)) => x == y,
+            (Self::Instance(x), Self::Instance(y)) => eq_once(x, y),
+            (Self::Enum(x), Self::Enum(y)) => x == y,
+            (Self::EnumValue(x), Self::EnumValue(y)) => x == y,
+            (Self::Function(x), Self::Function(y)) => x == y,
+            (Self::Closure(x), Self::Closure(y)) => x == y,
+            (Self::NativeFunction(x), Self::NativeFunction(y)) => x == y,
+            (Self::BoundMethod(x), Self::BoundMethod(y)) => x == y,
+            (Self::VariantConstructor(x), Self::VariantConstructor(y)) => x == y,
+            (Self::Module(x), Self::Module(y)) => x == y,
+            (Self::Error(x), Self::Error(y)) => Rc::ptr_eq(x, y),
+            (
+                Self::Range {
+                    start: x_start,
+                    end: x_end,
+                    inclusive: x_inclusive,
+                },
+                Self::Range {
+                    start: y_start,
+                    end: y_end,
+                    inclusive: y_inclusive,
+                },
+            ) => x_start == y_start && x_end == y_end && x_inclusive == y_inclusive,
+            (Self::Nil, Self::Nil) => true,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use crate::general::{object::Object, token::Token};
+
+#[derive(Debug)]
+pub enum Pattern {
+    // `_` matches anything without binding it.
+    Wildcard,
+    Binding(Token),
+    Literal(Object),
+    Range {
+        start: Object,
+        end: Object,
+        inclusive: bool,
+    },
+    // `Shape::Rect(w, h)` or, for unit variants, just `Shape::Empty`.
+    Variant {
+        enumeration: Token,
+        variant: Token,
+        fields: Option<Vec<Pattern>>,
+    },
+    Tuple(Vec<Pattern>),
+    // `[first, rest..]`: the elements before and after an optional rest,
+    // which is either `..` or binds the remaining elements like `rest..`.
+    Array {
+        head: Vec<Pattern>,
+        rest: Option<Box<Pattern>>,
+        tail: Vec<Pattern>,
+    },
+    Or(Vec<Pattern>),
+}
+
+impl Pattern {
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            Self::Wildcard | Self::Binding(_) => true,
+            Self::Or(alternatives) => alternatives.iter().any(Pattern::is_irrefutable),
+            _ => false,
+        }
+    }
+
+    pub fn bindings(&self) -> Vec<&Token> {
+        match self {
+            Self::Binding(identifier) => vec![identifier],
+            Self::Variant {
+                fields: Some(fields),
+                ..
+            } => fields.iter().flat_map(Pattern::bindings).collect(),
+            Self::Tuple(elements) => elements.iter().flat_map(Pattern::bindings).collect(),
+            Self::Array { head, rest, tail } => head
+                .iter()
+                .chain(rest.as_deref())
+                .chain(tail)
+                .flat_map(Pattern::bindings)
+                .collect(),
+            // Every alternative binds the same names, so the first one is
+            // representative for all of them.
+            Self::Or(alternatives) => alternatives
+                .first()
+                .map(Pattern::bindings)
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    // A variant pattern whose fields can not fail covers the whole variant.
+    fn covered_variant(&self) -> Option<(String, String)> {
+        match self {
+            Self::Variant {
+                enumeration,
+                variant,
+                fields,
+            } if fields.iter().flatten().all(Pattern::is_irrefutable) => {
+                Some((enumeration.lexeme.clone(), variant.lexeme.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+// Tracks what the unguarded arms of a match have handled so far, which is
+// enough to spot unreachable arms and, for booleans and enums declared
+// earlier in the file, missing ones.
+#[derive(Default)]
+pub struct Coverage {
+    catch_all: bool,
+    literals: Vec<Object>,
+    variants: Vec<(String, String)>,
+}
+
+impl Coverage {
+    pub fn covers(&self, pattern: &Pattern) -> bool {
+        if self.catch_all {
+            return true;
+        }
+        match pattern {
+            Pattern::Literal(literal) => self.literals.contains(literal),
+            Pattern::Variant { .. } => pattern
+                .covered_variant()
+                .is_some_and(|variant| self.variants.contains(&variant)),
+            Pattern::Or(alternatives) => alternatives.iter().all(|pattern| self.covers(pattern)),
+            _ => false,
+        }
+    }
+
+    pub fn add(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Wildcard | Pattern::Binding(_) => self.catch_all = true,
+            Pattern::Literal(literal) => self.literals.push(literal.clone()),
+            Pattern::Variant { .. } => self.variants.extend(pattern.covered_variant()),
+            Pattern::Or(alternatives) => alternatives.iter().for_each(|pattern| self.add(pattern)),
+            Pattern::Range { .. } | Pattern::Tuple(_) | Pattern::Array { .. } => {}
+        }
+    }
+
+    // Returns a description of what is missing, or None when the match is
+    // exhaustive or there is not enough information to tell.
+    pub fn missing(&self, enums: &HashMap<String, Vec<String>>) -> Option<String> {
+        if self.catch_all
+            || self.literals.contains(&Object::Boolean(true))
+                && self.literals.contains(&Object::Boolean(false))
+        {
+            return None;
+        }
+        let Some((enumeration, _)) = self.variants.first() else {
+            return Some("consider adding a '_' arm".to_string());
+        };
+        let variants = enums.get(enumeration)?;
+        let missing: Vec<String> = variants
+            .iter()
+            .filter(|variant| {
+                !self
+                    .variants
+                    .contains(&(enumeration.clone(), variant.to_string()))
+            })
+            .map(|variant| format!("'{}::{}'", enumeration, variant))
+            .collect();
+        if missing.is_empty() {
+            None
+        } else {
+            Some(format!("missing {}", missing.join(", ")))
+        }
+    }
+}
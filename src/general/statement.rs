@@ -1,36 +1,67 @@
-use crate::general::{error::Error, expression::Expression, token::Token};
+use std::rc::Rc;
+
+use crate::general::{
+    error::Interrupt,
+    expression::{BlockExpression, Expression},
+    pattern::Pattern,
+    token::Token,
+};
 
 pub trait StatementVisitor<T> {
-    fn visit_expression_statement(&self, statement: &ExpressionStatement) -> Result<T, Error>;
-    fn visit_let_statement(&mut self, statement: &LetStatement) -> Result<T, Error>;
-    fn visit_assignment_statement(&mut self, statement: &AssignmentExpression) -> Result<T, Error>;
-    fn visit_block_statement(&mut self, statement: &BlockStatement) -> Result<T, Error>;
-    fn visit_if_statement(&mut self, statement: &IfStatement) -> Result<T, Error>;
-    fn visit_while_statement(&mut self, statement: &WhileStatement) -> Result<T, Error>;
-    fn visit_print_statement(&self, statement: &PrintStatement) -> Result<T, Error>;
+    fn visit_expression_statement(
+        &mut self,
+        statement: &ExpressionStatement,
+    ) -> Result<T, Interrupt>;
+    fn visit_let_statement(&mut self, statement: &LetStatement) -> Result<T, Interrupt>;
+    fn visit_const_statement(&mut self, statement: &ConstStatement) -> Result<T, Interrupt>;
+    fn visit_while_statement(&mut self, statement: &WhileStatement) -> Result<T, Interrupt>;
+    fn visit_for_statement(&mut self, statement: &ForStatement) -> Result<T, Interrupt>;
+    fn visit_print_statement(&mut self, statement: &PrintStatement) -> Result<T, Interrupt>;
+    fn visit_function_statement(&mut self, statement: &FunctionStatement) -> Result<T, Interrupt>;
+    fn visit_struct_statement(&mut self, statement: &StructStatement) -> Result<T, Interrupt>;
+    fn visit_enum_statement(&mut self, statement: &EnumStatement) -> Result<T, Interrupt>;
+    fn visit_impl_statement(&mut self, statement: &ImplStatement) -> Result<T, Interrupt>;
+    fn visit_return_statement(&mut self, statement: &ReturnStatement) -> Result<T, Interrupt>;
+    fn visit_break_statement(&mut self, statement: &BreakStatement) -> Result<T, Interrupt>;
+    fn visit_continue_statement(&mut self, statement: &ContinueStatement) -> Result<T, Interrupt>;
+    fn visit_use_statement(&mut self, statement: &UseStatement) -> Result<T, Interrupt>;
 }
 
 #[derive(Debug)]
 pub enum Statement {
     Expression(ExpressionStatement),
     Let(LetStatement),
-    Assignment(AssignmentExpression),
-    Block(BlockStatement),
-    If(IfStatement),
+    Const(ConstStatement),
     While(WhileStatement),
+    For(ForStatement),
     Print(PrintStatement),
+    Function(FunctionStatement),
+    Struct(StructStatement),
+    Enum(EnumStatement),
+    Impl(ImplStatement),
+    Return(ReturnStatement),
+    Break(BreakStatement),
+    Continue(ContinueStatement),
+    Use(UseStatement),
 }
 
 impl Statement {
-    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Error> {
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
         match self {
             Self::Expression(statement) => statement.accept(visitor),
             Self::Let(statement) => statement.accept(visitor),
-            Self::Assignment(statement) => statement.accept(visitor),
-            Self::Block(statement) => statement.accept(visitor),
-            Self::If(statement) => statement.accept(visitor),
+            Self::Const(statement) => statement.accept(visitor),
             Self::While(statement) => statement.accept(visitor),
+            Self::For(statement) => statement.accept(visitor),
             Self::Print(statement) => statement.accept(visitor),
+            Self::Function(statement) => statement.accept(visitor),
+            Self::Struct(statement) => statement.accept(visitor),
+            Self::Enum(statement) => statement.accept(visitor),
+            Self::Impl(statement) => statement.accept(visitor),
+            Self::Return(statement) => statement.accept(visitor),
+            Self::Break(statement) => statement.accept(visitor),
+            Self::Continue(statement) => statement.accept(visitor),
+            Self::Use(statement) => statement.accept(visitor),
         }
     }
 }
@@ -45,116 +76,273 @@ impl ExpressionStatement {
         Self { expression }
     }
 
-    pub fn accept<T>(&self, visitor: &dyn StatementVisitor<T>) -> Result<T, Error> {
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
         visitor.visit_expression_statement(self)
     }
 }
 
 #[derive(Debug)]
 pub struct LetStatement {
-    pub identifier: Token,
+    pub keyword: Token,
+    // Set by `let mut`, without it the bindings can not be assigned to.
+    pub mutable: bool,
+    pub pattern: Pattern,
     pub value: Expression,
 }
 
 impl LetStatement {
-    pub fn new(identifier: Token, value: Expression) -> Self {
-        Self { identifier, value }
+    pub fn new(keyword: Token, mutable: bool, pattern: Pattern, value: Expression) -> Self {
+        Self {
+            keyword,
+            mutable,
+            pattern,
+            value,
+        }
     }
 
-    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Error> {
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
         visitor.visit_let_statement(self)
     }
 }
 
 #[derive(Debug)]
-pub struct AssignmentExpression {
+pub struct ConstStatement {
     pub identifier: Token,
     pub value: Expression,
 }
 
-impl AssignmentExpression {
+impl ConstStatement {
     pub fn new(identifier: Token, value: Expression) -> Self {
         Self { identifier, value }
     }
 
-    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Error> {
-        visitor.visit_assignment_statement(self)
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_const_statement(self)
     }
 }
 
 #[derive(Debug)]
-pub struct BlockStatement {
-    pub statements: Vec<Statement>,
+pub struct WhileStatement {
+    pub label: Option<Token>,
+    pub conditional: Expression,
+    pub block: BlockExpression,
 }
 
-impl BlockStatement {
-    pub fn new(statements: Vec<Statement>) -> Self {
-        Self { statements }
+impl WhileStatement {
+    pub fn new(label: Option<Token>, conditional: Expression, block: BlockExpression) -> Self {
+        Self {
+            label,
+            conditional,
+            block,
+        }
     }
 
-    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Error> {
-        visitor.visit_block_statement(self)
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_while_statement(self)
     }
 }
 
 #[derive(Debug)]
-pub struct IfStatement {
-    pub conditional: Expression,
-    pub if_block: Box<Statement>,
-    pub else_block: Option<Box<Statement>>,
+pub struct ForStatement {
+    pub label: Option<Token>,
+    pub variable: Token,
+    pub iterable: Expression,
+    pub block: BlockExpression,
 }
 
-impl IfStatement {
+impl ForStatement {
     pub fn new(
-        conditional: Expression,
-        if_block: Statement,
-        else_block: Option<Statement>,
+        label: Option<Token>,
+        variable: Token,
+        iterable: Expression,
+        block: BlockExpression,
     ) -> Self {
         Self {
-            conditional,
-            if_block: Box::new(if_block),
-            else_block: match else_block {
-                Some(block) => Some(Box::new(block)),
-                None => None,
-            },
+            label,
+            variable,
+            iterable,
+            block,
         }
     }
 
-    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Error> {
-        visitor.visit_if_statement(self)
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_for_statement(self)
     }
 }
 
 #[derive(Debug)]
-pub struct WhileStatement {
-    pub conditional: Expression,
-    pub block: Box<Statement>,
+pub struct PrintStatement {
+    pub values: Vec<Expression>,
 }
 
-impl WhileStatement {
-    pub fn new(conditional: Expression, block: Statement) -> Self {
+impl PrintStatement {
+    pub fn new(values: Vec<Expression>) -> Self {
+        Self { values }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_print_statement(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct FunctionStatement {
+    pub identifier: Token,
+    pub parameters: Vec<Token>,
+    pub body: Rc<Vec<Statement>>,
+}
+
+impl FunctionStatement {
+    pub fn new(identifier: Token, parameters: Vec<Token>, body: Vec<Statement>) -> Self {
         Self {
-            conditional,
-            block: Box::new(block),
+            identifier,
+            parameters,
+            body: Rc::new(body),
         }
     }
 
-    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Error> {
-        visitor.visit_while_statement(self)
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_function_statement(self)
     }
 }
 
 #[derive(Debug)]
-pub struct PrintStatement {
-    pub values: Vec<Expression>,
+pub struct StructStatement {
+    pub identifier: Token,
+    pub fields: Vec<Token>,
 }
 
-impl PrintStatement {
-    pub fn new(values: Vec<Expression>) -> Self {
-        Self { values }
+impl StructStatement {
+    pub fn new(identifier: Token, fields: Vec<Token>) -> Self {
+        Self { identifier, fields }
     }
 
-    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Error> {
-        visitor.visit_print_statement(self)
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_struct_statement(self)
+    }
+}
+
+// A unit variant like `Empty` has no fields, while a tuple variant like
+// `Circle(r)` has a (possibly empty) list of them.
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub identifier: Token,
+    pub fields: Option<Vec<Token>>,
+}
+
+impl Variant {
+    pub fn new(identifier: Token, fields: Option<Vec<Token>>) -> Self {
+        Self { identifier, fields }
+    }
+}
+
+#[derive(Debug)]
+pub struct EnumStatement {
+    pub identifier: Token,
+    pub variants: Vec<Variant>,
+}
+
+impl EnumStatement {
+    pub fn new(identifier: Token, variants: Vec<Variant>) -> Self {
+        Self {
+            identifier,
+            variants,
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_enum_statement(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct ImplStatement {
+    pub identifier: Token,
+    pub methods: Vec<FunctionStatement>,
+}
+
+impl ImplStatement {
+    pub fn new(identifier: Token, methods: Vec<FunctionStatement>) -> Self {
+        Self {
+            identifier,
+            methods,
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_impl_statement(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct ReturnStatement {
+    pub value: Option<Expression>,
+}
+
+impl ReturnStatement {
+    pub fn new(value: Option<Expression>) -> Self {
+        Self { value }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_return_statement(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct BreakStatement {
+    pub label: Option<Token>,
+    // Only a 'loop' can be left with a value.
+    pub value: Option<Expression>,
+}
+
+impl BreakStatement {
+    pub fn new(label: Option<Token>, value: Option<Expression>) -> Self {
+        Self { label, value }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_break_statement(self)
+    }
+}
+
+#[derive(Debug)]
+pub struct ContinueStatement {
+    pub label: Option<Token>,
+}
+
+impl ContinueStatement {
+    pub fn new(label: Option<Token>) -> Self {
+        Self { label }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_continue_statement(self)
+    }
+}
+
+// `mod math;`, `use "lib/math.dvr";` and `use math::{gcd, lcm};` all load a
+// file relative to the importing one, then bind the module itself, some of
+// its items, or both.
+#[derive(Debug)]
+pub struct UseStatement {
+    pub keyword: Token,
+    pub path: String,
+    pub module: Option<Token>,
+    pub items: Vec<Token>,
+}
+
+impl UseStatement {
+    pub fn new(keyword: Token, path: String, module: Option<Token>, items: Vec<Token>) -> Self {
+        Self {
+            keyword,
+            path,
+            module,
+            items,
+        }
+    }
+
+    pub fn accept<T>(&self, visitor: &mut dyn StatementVisitor<T>) -> Result<T, Interrupt> {
+        visitor.visit_use_statement(self)
     }
 }
@@ -2,21 +2,46 @@ use std::fmt::{Display, Formatter, Result};
 
 use crate::general::object::Object;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     Number,
 
     Identifier,
+    Label,
 
     Let,
+    Mut,
+    Const,
     If,
     Else,
     While,
+    For,
+    In,
+    Not,
+    Fn,
+    Struct,
+    Enum,
+    Impl,
+    Match,
+    Loop,
+    Try,
+    Catch,
+    Return,
+    Break,
+    Continue,
+    Pub,
+    Mod,
+    Use,
 
     Nil,
     True,
     False,
     String,
+    FormatStart,
+    FormatEnd,
+    InterpolationStart,
+    InterpolationEnd,
 
     Print,
 
@@ -24,29 +49,54 @@ pub enum TokenType {
     CloseParen,
     OpenCurly,
     CloseCurly,
+    OpenBracket,
+    CloseBracket,
 
     Plus,
     Minus,
     Multiplication,
     Division,
     Modulo,
+    Power,
+    FloorDivision,
 
     Comma,
+    Dot,
+    DotDot,
+    DotDotEqual,
+    Colon,
+    ColonColon,
     Semicolon,
 
     Equal,
+    PlusEqual,
+    MinusEqual,
+    MultiplicationEqual,
+    DivisionEqual,
+    ModuloEqual,
+    BitwiseAndEqual,
+    BitwiseOrEqual,
+    BitwiseXorEqual,
+    ShiftLeftEqual,
+    ShiftRightEqual,
     EqualEqual,
+    FatArrow,
     Bang,
     BangEqual,
     Greater,
     GreaterEqual,
     Less,
     LessEqual,
+    NotIn,
 
     BitwiseAnd,
     And,
     BitwiseOr,
     Or,
+    BitwiseXor,
+    BitwiseNot,
+    ShiftLeft,
+    ShiftRight,
 
     EOF,
 }
@@ -57,16 +107,40 @@ impl Display for TokenType {
             Self::Number => write!(f, "Number"),
 
             Self::Identifier => write!(f, "Identifier"),
+            Self::Label => write!(f, "Label"),
 
             Self::Let => write!(f, "Let"),
+            Self::Mut => write!(f, "Mut"),
+            Self::Const => write!(f, "Const"),
             Self::If => write!(f, "If"),
             Self::Else => write!(f, "Else"),
             Self::While => write!(f, "While"),
+            Self::For => write!(f, "For"),
+            Self::In => write!(f, "In"),
+            Self::Not => write!(f, "Not"),
+            Self::Fn => write!(f, "Fn"),
+            Self::Struct => write!(f, "Struct"),
+            Self::Enum => write!(f, "Enum"),
+            Self::Impl => write!(f, "Impl"),
+            Self::Match => write!(f, "Match"),
+            Self::Loop => write!(f, "Loop"),
+            Self::Try => write!(f, "Try"),
+            Self::Catch => write!(f, "Catch"),
+            Self::Return => write!(f, "Return"),
+            Self::Break => write!(f, "Break"),
+            Self::Continue => write!(f, "Continue"),
+            Self::Pub => write!(f, "Pub"),
+            Self::Mod => write!(f, "Mod"),
+            Self::Use => write!(f, "Use"),
 
             Self::Nil => write!(f, "Nil"),
             Self::True => write!(f, "True"),
             Self::False => write!(f, "False"),
             Self::String => write!(f, "String"),
+            Self::FormatStart => write!(f, "FormatStart"),
+            Self::FormatEnd => write!(f, "FormatEnd"),
+            Self::InterpolationStart => write!(f, "InterpolationStart"),
+            Self::InterpolationEnd => write!(f, "InterpolationEnd"),
 
             Self::Print => write!(f, "Print"),
 
@@ -74,29 +148,54 @@ impl Display for TokenType {
             Self::CloseParen => write!(f, "CloseParen"),
             Self::OpenCurly => write!(f, "OpenCurly"),
             Self::CloseCurly => write!(f, "CloseCurly"),
+            Self::OpenBracket => write!(f, "OpenBracket"),
+            Self::CloseBracket => write!(f, "CloseBracket"),
 
             Self::Plus => write!(f, "Plus"),
             Self::Minus => write!(f, "Minus"),
             Self::Multiplication => write!(f, "Multiplication"),
             Self::Division => write!(f, "Division"),
             Self::Modulo => write!(f, "Modulo"),
+            Self::Power => write!(f, "Power"),
+            Self::FloorDivision => write!(f, "FloorDivision"),
 
             Self::Comma => write!(f, "Comma"),
+            Self::Dot => write!(f, "Dot"),
+            Self::DotDot => write!(f, "DotDot"),
+            Self::DotDotEqual => write!(f, "DotDotEqual"),
+            Self::Colon => write!(f, "Colon"),
+            Self::ColonColon => write!(f, "ColonColon"),
             Self::Semicolon => write!(f, "Semicolon"),
 
             Self::Equal => write!(f, "Equal"),
+            Self::PlusEqual => write!(f, "PlusEqual"),
+            Self::MinusEqual => write!(f, "MinusEqual"),
+            Self::MultiplicationEqual => write!(f, "MultiplicationEqual"),
+            Self::DivisionEqual => write!(f, "DivisionEqual"),
+            Self::ModuloEqual => write!(f, "ModuloEqual"),
+            Self::BitwiseAndEqual => write!(f, "BitwiseAndEqual"),
+            Self::BitwiseOrEqual => write!(f, "BitwiseOrEqual"),
+            Self::BitwiseXorEqual => write!(f, "BitwiseXorEqual"),
+            Self::ShiftLeftEqual => write!(f, "ShiftLeftEqual"),
+            Self::ShiftRightEqual => write!(f, "ShiftRightEqual"),
             Self::EqualEqual => write!(f, "EqualEqual"),
+            Self::FatArrow => write!(f, "FatArrow"),
             Self::Bang => write!(f, "Bang"),
             Self::BangEqual => write!(f, "BangEqual"),
             Self::Greater => write!(f, "Greater"),
             Self::GreaterEqual => write!(f, "GreaterEqual"),
             Self::Less => write!(f, "Less"),
             Self::LessEqual => write!(f, "LessEqual"),
+            Self::NotIn => write!(f, "NotIn"),
 
             Self::BitwiseAnd => write!(f, "BitwiseAnd"),
             Self::And => write!(f, "And"),
             Self::BitwiseOr => write!(f, "BitwiseOr"),
             Self::Or => write!(f, "Or"),
+            Self::BitwiseXor => write!(f, "BitwiseXor"),
+            Self::BitwiseNot => write!(f, "BitwiseNot"),
+            Self::ShiftLeft => write!(f, "ShiftLeft"),
+            Self::ShiftRight => write!(f, "ShiftRight"),
 
             Self::EOF => write!(f, "EOF"),
         }
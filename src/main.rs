@@ -7,6 +7,7 @@ use std::{
     fs::read_to_string,
     io::{stdin, stdout, Write},
     process::exit,
+    thread,
 };
 
 use crate::{
@@ -15,8 +16,18 @@ use crate::{
     runtime::interpreter::Interpreter,
 };
 
+// The interpreter recurses for every nested call, so it gets a stack large
+// enough for the interpreter's call depth limit even in a debug build.
+const STACK_SIZE: usize = 1 << 30;
+
 fn main() {
-    run().unwrap_or_else(|error| error.throw());
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(run)
+        .unwrap()
+        .join()
+        .unwrap()
+        .unwrap_or_else(|error| error.throw());
 }
 
 fn run() -> Result<(), Error> {
@@ -37,12 +48,15 @@ fn run() -> Result<(), Error> {
             filepath => {
                 if let Ok(source) = read_to_string(filepath) {
                     let mut lexer = Lexer::new(&source);
-                    let tokens = lexer.lex()?;
+                    let tokens = lexer.lex().map_err(|error| error.in_file(filepath))?;
 
                     let mut parser = Parser::new(tokens);
-                    let statements = parser.parse()?;
+                    let statements = parser.parse().map_err(|error| error.in_file(filepath))?;
+                    for warning in parser.warnings() {
+                        eprintln!("{}", warning.clone().in_file(filepath));
+                    }
 
-                    let mut interpreter = Interpreter::new();
+                    let mut interpreter = Interpreter::with_file(filepath);
                     interpreter.run(statements)?;
                 } else {
                     print_help(Some(&format!(
@@ -76,6 +90,9 @@ fn repl() -> Result<(), Error> {
 
         let mut parser = Parser::new(tokens);
         let statements = parser.parse()?;
+        for warning in parser.warnings() {
+            eprintln!("{}", warning);
+        }
 
         let mut interpreter = Interpreter::new();
         interpreter.run(statements)?;
@@ -0,0 +1,152 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    general::{bigint::BigInt, error::ErrorType, map::MapKey, object::Object},
+    runtime::callable::NativeFunction,
+};
+
+pub fn builtins() -> Vec<NativeFunction> {
+    vec![
+        NativeFunction::new("len", 1, len),
+        NativeFunction::new("push", 2, push),
+        NativeFunction::new("pop", 1, pop),
+        NativeFunction::new("keys", 1, keys),
+        NativeFunction::new("values", 1, values),
+        NativeFunction::new("contains_key", 2, contains_key),
+        NativeFunction::new("remove", 2, remove),
+        NativeFunction::new("int", 1, int),
+        NativeFunction::new("float", 1, float),
+        NativeFunction::new("str", 1, str),
+        NativeFunction::new("panic", 1, panic).raising(ErrorType::Panic),
+    ]
+}
+
+fn len(arguments: Vec<Object>) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Array(array) => Ok(Object::Integer(array.borrow().len() as i64)),
+        Object::Tuple(elements) => Ok(Object::Integer(elements.len() as i64)),
+        Object::String(string) => Ok(Object::Integer(string.chars().count() as i64)),
+        Object::Map(map) => Ok(Object::Integer(map.borrow().len() as i64)),
+        argument => Err(format!(
+            "Expected array, tuple, map or string in 'len', found '{}'",
+            argument
+        )),
+    }
+}
+
+fn push(arguments: Vec<Object>) -> Result<Object, String> {
+    let mut arguments = arguments.into_iter();
+    match (arguments.next(), arguments.next()) {
+        (Some(Object::Array(array)), Some(value)) => {
+            array.borrow_mut().push(value);
+            Ok(Object::Nil)
+        }
+        (argument, _) => Err(format!(
+            "Expected array in 'push', found '{}'",
+            argument.unwrap_or(Object::Nil)
+        )),
+    }
+}
+
+fn pop(arguments: Vec<Object>) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Array(array) => array
+            .borrow_mut()
+            .pop()
+            .ok_or_else(|| "Can not pop from an empty array".to_string()),
+        argument => Err(format!("Expected array in 'pop', found '{}'", argument)),
+    }
+}
+
+fn keys(arguments: Vec<Object>) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Map(map) => {
+            let keys = map.borrow().keys().map(MapKey::to_object).collect();
+            Ok(Object::Array(Rc::new(RefCell::new(keys))))
+        }
+        argument => Err(format!("Expected map in 'keys', found '{}'", argument)),
+    }
+}
+
+fn values(arguments: Vec<Object>) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Map(map) => {
+            let values = map.borrow().values().cloned().collect();
+            Ok(Object::Array(Rc::new(RefCell::new(values))))
+        }
+        argument => Err(format!("Expected map in 'values', found '{}'", argument)),
+    }
+}
+
+fn contains_key(arguments: Vec<Object>) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Map(map) => {
+            let key = map_key(&arguments[1])?;
+            Ok(Object::Boolean(map.borrow().contains_key(&key)))
+        }
+        argument => Err(format!(
+            "Expected map in 'contains_key', found '{}'",
+            argument
+        )),
+    }
+}
+
+// Removing a key that is not in the map is not an error, it just gives nil.
+fn remove(arguments: Vec<Object>) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Map(map) => {
+            let key = map_key(&arguments[1])?;
+            Ok(map.borrow_mut().remove(&key).unwrap_or(Object::Nil))
+        }
+        argument => Err(format!("Expected map in 'remove', found '{}'", argument)),
+    }
+}
+
+// Floats are truncated towards zero, strings are parsed.
+fn int(arguments: Vec<Object>) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Integer(_) | Object::BigInt(_) => Ok(arguments[0].clone()),
+        Object::Float(float) if float.is_finite() => {
+            Ok(Object::integer(BigInt::from_f64(*float).unwrap()))
+        }
+        Object::Boolean(boolean) => Ok(Object::Integer(*boolean as i64)),
+        Object::String(string) => string
+            .trim()
+            .parse()
+            .map(Object::integer)
+            .map_err(|_| format!("Can not convert '{}' to an integer", string)),
+        argument => Err(format!("Can not convert '{}' to an integer", argument)),
+    }
+}
+
+fn float(arguments: Vec<Object>) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Integer(_) | Object::BigInt(_) | Object::Float(_) => {
+            Ok(Object::Float(arguments[0].as_float().unwrap()))
+        }
+        Object::String(string) => string
+            .trim()
+            .parse()
+            .map(Object::Float)
+            .map_err(|_| format!("Can not convert '{}' to a float", string)),
+        argument => Err(format!("Can not convert '{}' to a float", argument)),
+    }
+}
+
+fn str(arguments: Vec<Object>) -> Result<Object, String> {
+    Ok(Object::String(arguments[0].to_string()))
+}
+
+// Always fails, stopping the program unless a 'try' catches it.
+fn panic(arguments: Vec<Object>) -> Result<Object, String> {
+    Err(arguments[0].to_string())
+}
+
+fn map_key(key: &Object) -> Result<MapKey, String> {
+    MapKey::from_object(key).ok_or_else(|| {
+        format!(
+            "Expected string, number or boolean as map key, found '{}'",
+            key
+        )
+    })
+}
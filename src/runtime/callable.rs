@@ -0,0 +1,220 @@
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Display, Formatter, Result},
+    ptr,
+    rc::Rc,
+};
+
+use crate::{
+    general::{error::ErrorType, object::Object, statement::Statement, token::Token},
+    runtime::{environment::Environment, instance::EnumDefinition},
+};
+
+pub struct Function {
+    pub identifier: Token,
+    pub parameters: Vec<Token>,
+    pub body: Rc<Vec<Statement>>,
+    pub closure: Rc<RefCell<Environment>>,
+}
+
+impl Function {
+    pub fn new(
+        identifier: Token,
+        parameters: Vec<Token>,
+        body: Rc<Vec<Statement>>,
+        closure: Rc<RefCell<Environment>>,
+    ) -> Self {
+        Self {
+            identifier,
+            parameters,
+            body,
+            closure,
+        }
+    }
+
+    pub fn arity(&self) -> usize {
+        self.parameters.len()
+    }
+
+    // Functions declared in an 'impl' block whose first parameter is 'self'
+    // are methods, every other one is an associated function.
+    pub fn is_method(&self) -> bool {
+        self.parameters
+            .first()
+            .is_some_and(|parameter| parameter.lexeme == "self")
+    }
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+    }
+}
+
+impl Debug for Function {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "<fn {}>", self.identifier.lexeme)
+    }
+}
+
+pub struct Closure {
+    pub parameters: Vec<Token>,
+    pub body: Rc<Vec<Statement>>,
+    pub environment: Rc<RefCell<Environment>>,
+}
+
+impl Closure {
+    pub fn new(
+        parameters: Vec<Token>,
+        body: Rc<Vec<Statement>>,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Self {
+        Self {
+            parameters,
+            body,
+            environment,
+        }
+    }
+
+    pub fn arity(&self) -> usize {
+        self.parameters.len()
+    }
+}
+
+impl PartialEq for Closure {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+    }
+}
+
+impl Debug for Closure {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for Closure {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "<closure>")
+    }
+}
+
+pub struct NativeFunction {
+    pub name: &'static str,
+    pub arity: usize,
+    pub function: fn(Vec<Object>) -> std::result::Result<Object, String>,
+    // The kind of error a failing call raises.
+    pub raises: ErrorType,
+}
+
+impl NativeFunction {
+    pub fn new(
+        name: &'static str,
+        arity: usize,
+        function: fn(Vec<Object>) -> std::result::Result<Object, String>,
+    ) -> Self {
+        Self {
+            name,
+            arity,
+            function,
+            raises: ErrorType::RuntimeError,
+        }
+    }
+
+    pub fn raising(mut self, raises: ErrorType) -> Self {
+        self.raises = raises;
+        self
+    }
+}
+
+impl PartialEq for NativeFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Debug for NativeFunction {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for NativeFunction {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "<native fn {}>", self.name)
+    }
+}
+
+#[derive(PartialEq)]
+pub struct BoundMethod {
+    pub receiver: Object,
+    pub method: Rc<Function>,
+}
+
+impl BoundMethod {
+    pub fn new(receiver: Object, method: Rc<Function>) -> Self {
+        Self { receiver, method }
+    }
+
+    pub fn arity(&self) -> usize {
+        self.method.arity() - 1
+    }
+}
+
+impl Debug for BoundMethod {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for BoundMethod {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "<method {}>", self.method.identifier.lexeme)
+    }
+}
+
+// Calling `Shape::Circle` with the variant's fields builds a `Shape::Circle(..)`.
+#[derive(PartialEq)]
+pub struct VariantConstructor {
+    pub definition: Rc<EnumDefinition>,
+    pub variant: usize,
+}
+
+impl VariantConstructor {
+    pub fn new(definition: Rc<EnumDefinition>, variant: usize) -> Self {
+        Self {
+            definition,
+            variant,
+        }
+    }
+
+    pub fn arity(&self) -> usize {
+        self.definition.variants[self.variant]
+            .fields
+            .as_ref()
+            .map_or(0, |fields| fields.len())
+    }
+}
+
+impl Debug for VariantConstructor {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for VariantConstructor {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(
+            f,
+            "<variant {}::{}>",
+            self.definition.identifier.lexeme,
+            self.definition.variants[self.variant].identifier.lexeme
+        )
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use crate::general::{
     error::{Error, ErrorType},
@@ -6,25 +6,119 @@ use crate::general::{
     token::Token,
 };
 
+#[derive(Debug, Clone)]
+struct Binding {
+    value: Object,
+    mutable: bool,
+    // Where the binding was declared, None for builtins.
+    line: Option<usize>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Environment {
-    bindings: HashMap<String, Object>,
+    bindings: HashMap<String, Binding>,
+    parent: Option<Rc<RefCell<Environment>>>,
+    // The source file whose code runs in this environment, inherited from
+    // the parent. None in the repl.
+    file: Option<Rc<str>>,
 }
 
 impl Environment {
     pub fn new() -> Self {
         Self {
             bindings: HashMap::new(),
+            parent: None,
+            file: None,
+        }
+    }
+
+    pub fn with_file(file: &str) -> Self {
+        Self {
+            bindings: HashMap::new(),
+            parent: None,
+            file: Some(Rc::from(file)),
+        }
+    }
+
+    pub fn with_parent(parent: Rc<RefCell<Environment>>) -> Self {
+        let file = parent.borrow().file.clone();
+        Self {
+            bindings: HashMap::new(),
+            parent: Some(parent),
+            file,
+        }
+    }
+
+    pub fn file(&self) -> Option<Rc<str>> {
+        self.file.clone()
+    }
+
+    // Bindings are immutable unless defined with `define_mutable`.
+    pub fn define(&mut self, identifier: &Token, value: Object) {
+        self.insert(identifier, value, false);
+    }
+
+    pub fn define_mutable(&mut self, identifier: &Token, value: Object) {
+        self.insert(identifier, value, true);
+    }
+
+    pub fn define_builtin(&mut self, name: &str, value: Object) {
+        let binding = Binding {
+            value,
+            mutable: false,
+            line: None,
+        };
+        self.bindings.insert(name.to_string(), binding);
+    }
+
+    fn insert(&mut self, identifier: &Token, value: Object, mutable: bool) {
+        let binding = Binding {
+            value,
+            mutable,
+            line: Some(identifier.line),
+        };
+        self.bindings.insert(identifier.lexeme.clone(), binding);
+    }
+
+    pub fn assign(&mut self, identifier: &Token, value: Object) -> Result<(), Error> {
+        if let Some(binding) = self.bindings.get_mut(&identifier.lexeme) {
+            if !binding.mutable {
+                let message = match binding.line {
+                    Some(line) => format!(
+                        "Can not assign twice to immutable variable '{}' declared on line {}",
+                        identifier.lexeme, line
+                    ),
+                    None => format!("Can not assign to builtin '{}'", identifier.lexeme),
+                };
+                return Err(Error::new(
+                    ErrorType::RuntimeError,
+                    &message,
+                    identifier.line,
+                ));
+            }
+            binding.value = value;
+            Ok(())
+        } else if let Some(parent) = &self.parent {
+            parent.borrow_mut().assign(identifier, value)
+        } else {
+            Err(Error::new(
+                ErrorType::RuntimeError,
+                &format!("Variable with name '{}' not found", identifier.lexeme),
+                identifier.line,
+            ))
         }
     }
 
-    pub fn set(&mut self, identifier: &Token, value: Object) {
-        self.bindings.insert(identifier.lexeme.clone(), value);
+    // Looks `name` up in this environment only, ignoring the parents.
+    pub fn get_local(&self, name: &str) -> Option<Object> {
+        self.bindings.get(name).map(|binding| binding.value.clone())
     }
 
     pub fn get(&self, identifier: &Token) -> Result<Object, Error> {
-        if let Some(value) = self.bindings.get(&identifier.lexeme) {
-            Ok(value.clone())
+        if let Some(binding) = self.bindings.get(&identifier.lexeme) {
+            Ok(binding.value.clone())
+        } else if let Some(parent) = &self.parent {
+            parent.borrow().get(identifier)
         } else {
             Err(Error::new(
                 ErrorType::RuntimeError,
@@ -0,0 +1,191 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Debug, Display, Formatter, Result},
+    ptr,
+    rc::Rc,
+};
+
+use crate::{
+    general::{object::Object, statement::Variant, token::Token},
+    runtime::callable::Function,
+};
+
+pub struct StructDefinition {
+    pub identifier: Token,
+    pub fields: Vec<Token>,
+    // Filled in by 'impl' blocks, which may come after the declaration.
+    pub methods: RefCell<HashMap<String, Rc<Function>>>,
+}
+
+impl StructDefinition {
+    pub fn new(identifier: Token, fields: Vec<Token>) -> Self {
+        Self {
+            identifier,
+            fields,
+            methods: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn method(&self, name: &str) -> Option<Rc<Function>> {
+        self.methods.borrow().get(name).cloned()
+    }
+
+    pub fn has_field(&self, name: &str) -> bool {
+        self.fields.iter().any(|field| field.lexeme == name)
+    }
+}
+
+impl PartialEq for StructDefinition {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+    }
+}
+
+impl Debug for StructDefinition {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for StructDefinition {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "<struct {}>", self.identifier.lexeme)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Instance {
+    pub definition: Rc<StructDefinition>,
+    // Kept in the order the fields were declared in, not the order they
+    // were written in at construction.
+    fields: Vec<(String, Object)>,
+}
+
+impl Instance {
+    pub fn new(definition: Rc<StructDefinition>, fields: Vec<(String, Object)>) -> Self {
+        Self { definition, fields }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Object> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub fn set(&mut self, name: &str, value: Object) -> bool {
+        match self.fields.iter_mut().find(|(field, _)| field == name) {
+            Some((_, field)) => {
+                *field = value;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Display for Instance {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.definition.identifier.lexeme)?;
+        if self.fields.is_empty() {
+            return Ok(());
+        }
+        write!(f, " {{ ")?;
+        for (index, (field, value)) in self.fields.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: ", field)?;
+            value.fmt_nested(f)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+pub struct EnumDefinition {
+    pub identifier: Token,
+    pub variants: Vec<Variant>,
+    pub methods: RefCell<HashMap<String, Rc<Function>>>,
+}
+
+impl EnumDefinition {
+    pub fn new(identifier: Token, variants: Vec<Variant>) -> Self {
+        Self {
+            identifier,
+            variants,
+            methods: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn variant(&self, name: &str) -> Option<usize> {
+        self.variants
+            .iter()
+            .position(|variant| variant.identifier.lexeme == name)
+    }
+
+    pub fn method(&self, name: &str) -> Option<Rc<Function>> {
+        self.methods.borrow().get(name).cloned()
+    }
+}
+
+impl PartialEq for EnumDefinition {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+    }
+}
+
+impl Debug for EnumDefinition {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for EnumDefinition {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "<enum {}>", self.identifier.lexeme)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EnumValue {
+    pub definition: Rc<EnumDefinition>,
+    pub variant: usize,
+    pub values: Vec<Object>,
+}
+
+impl EnumValue {
+    pub fn new(definition: Rc<EnumDefinition>, variant: usize, values: Vec<Object>) -> Self {
+        Self {
+            definition,
+            variant,
+            values,
+        }
+    }
+
+    pub fn variant_name(&self) -> &str {
+        &self.definition.variants[self.variant].identifier.lexeme
+    }
+}
+
+impl Display for EnumValue {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(
+            f,
+            "{}::{}",
+            self.definition.identifier.lexeme,
+            self.variant_name()
+        )?;
+        if self.definition.variants[self.variant].fields.is_none() {
+            return Ok(());
+        }
+        write!(f, "(")?;
+        for (index, value) in self.values.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            value.fmt_nested(f)?;
+        }
+        write!(f, ")")
+    }
+}
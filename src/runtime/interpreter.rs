@@ -1,108 +1,1141 @@
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::HashMap,
+    fs::read_to_string,
+    mem,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
 use crate::{
+    frontend::{lexer::Lexer, parser::Parser},
     general::{
-        error::{Error, ErrorType},
+        bigint::BigInt,
+        error::{Error, ErrorType, Interrupt},
         expression::{
-            BinaryExpression, Expression, ExpressionVisitor, GroupingExpression, LiteralExpression,
-            UnaryExpression, VariableExpression,
+            ArrayExpression, AssignmentExpression, BinaryExpression, BlockExpression,
+            CallExpression, DestructureExpression, Expression, ExpressionVisitor, FormatExpression,
+            FormatPart, GetExpression, GroupingExpression, IfExpression, IndexExpression,
+            IndexSetExpression, LambdaExpression, LiteralExpression, LogicalExpression,
+            LoopExpression, MapExpression, MatchExpression, PathExpression, RangeExpression,
+            SetExpression, StructExpression, TryExpression, TupleExpression, UnaryExpression,
+            VariableExpression,
         },
+        map::{Map, MapKey},
         object::Object,
+        pattern::Pattern,
         statement::{
-            AssignmentExpression, BlockStatement, ExpressionStatement, IfStatement, LetStatement,
-            PrintStatement, Statement, StatementVisitor, WhileStatement,
+            BreakStatement, ConstStatement, ContinueStatement, EnumStatement, ExpressionStatement,
+            ForStatement, FunctionStatement, ImplStatement, LetStatement, PrintStatement,
+            ReturnStatement, Statement, StatementVisitor, StructStatement, UseStatement,
+            WhileStatement,
         },
-        token::TokenType,
+        token::{Token, TokenType},
+    },
+    runtime::{
+        builtins::builtins,
+        callable::{BoundMethod, Closure, Function, VariantConstructor},
+        environment::Environment,
+        instance::{EnumDefinition, EnumValue, Instance, StructDefinition},
+        module::Module,
     },
-    runtime::environment::Environment,
 };
 
+// Keeps `1 << n` from trying to allocate more memory than there is.
+const MAX_SHIFT: i64 = 1 << 24;
+// Likewise for `x ** n`, whose result grows with n.
+const MAX_EXPONENT: i64 = 1 << 16;
+// Deep recursion is reported as an error before it overflows the native stack.
+const MAX_CALL_DEPTH: usize = 10_000;
+
 pub struct Interpreter {
-    environment: Environment,
+    environment: Rc<RefCell<Environment>>,
+    // Every module loaded so far, so each one only runs once.
+    modules: HashMap<PathBuf, Rc<Module>>,
+    // The files being loaded right now and the names they were loaded by,
+    // outermost first, used to detect import cycles.
+    loading: Vec<(PathBuf, String)>,
+    // The number of function calls currently being executed.
+    depth: usize,
 }
 
 impl Interpreter {
     pub fn new() -> Self {
         Self {
-            environment: Environment::new(),
+            environment: Rc::new(RefCell::new(globals(Environment::new()))),
+            modules: HashMap::new(),
+            loading: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    // Runs the code of `file`, resolving its imports relative to it.
+    pub fn with_file(file: &str) -> Self {
+        let mut interpreter = Self::new();
+        interpreter.environment = Rc::new(RefCell::new(globals(Environment::with_file(file))));
+        if let Ok(path) = Path::new(file).canonicalize() {
+            interpreter.loading.push((path, file.to_string()));
         }
+        interpreter
     }
 
     pub fn run(&mut self, statements: Vec<Statement>) -> Result<(), Error> {
         for statement in &statements {
-            self.execute(statement)?;
+            if let Err(Interrupt::Error(error)) = self.execute(statement) {
+                return Err(in_file(error, &self.environment));
+            }
         }
         Ok(())
     }
 
-    fn execute(&mut self, statement: &Statement) -> Result<Object, Error> {
+    // Loads the module at `path`, relative to the file being run, running
+    // it unless it was loaded before.
+    fn load(&mut self, path: &str, keyword: &Token) -> Result<Rc<Module>, Interrupt> {
+        let file = self.environment.borrow().file();
+        let directory = file
+            .as_deref()
+            .and_then(|file| Path::new(file).parent())
+            .unwrap_or(Path::new(""));
+        let name = directory.join(path).display().to_string();
+        let resolved = Path::new(&name)
+            .canonicalize()
+            .map_err(|_| self.error(&format!("Can not find module '{}'", name), keyword.line))?;
+        if let Some(module) = self.modules.get(&resolved) {
+            return Ok(Rc::clone(module));
+        }
+        if let Some(start) = self.loading.iter().position(|(file, _)| *file == resolved) {
+            let cycle: Vec<&str> = self.loading[start..]
+                .iter()
+                .map(|(_, name)| name.as_str())
+                .chain([name.as_str()])
+                .collect();
+            return Err(self.error(
+                &format!("Import cycle between modules: {}", cycle.join(" -> ")),
+                keyword.line,
+            ));
+        }
+        let source = read_to_string(&resolved)
+            .map_err(|_| self.error(&format!("Can not read module '{}'", name), keyword.line))?;
+        self.loading.push((resolved.clone(), name.clone()));
+        let exports = self.run_module(&source, &name);
+        self.loading.pop();
+        let module = Rc::new(Module::new(
+            name.clone(),
+            exports.map_err(|error| error.in_file(&name))?,
+        ));
+        self.modules.insert(resolved, Rc::clone(&module));
+        Ok(module)
+    }
+
+    // Runs a module in its own global environment, giving back the items it
+    // made public.
+    fn run_module(&mut self, source: &str, name: &str) -> Result<HashMap<String, Object>, Error> {
+        let tokens = Lexer::new(source).lex()?;
+        let mut parser = Parser::new(tokens);
+        let statements = parser.parse()?;
+        for warning in parser.warnings() {
+            eprintln!("{}", warning.clone().in_file(name));
+        }
+        let environment = globals(Environment::with_file(name));
+        let result = self.scoped(environment, |interpreter| {
+            for statement in &statements {
+                interpreter.execute(statement)?;
+            }
+            let environment = interpreter.environment.borrow();
+            Ok(parser
+                .exports()
+                .iter()
+                .filter_map(|export| {
+                    let value = environment.get_local(&export.lexeme)?;
+                    Some((export.lexeme.clone(), value))
+                })
+                .collect())
+        });
+        match result {
+            Err(Interrupt::Error(error)) => Err(error),
+            result => Ok(result.unwrap_or_default()),
+        }
+    }
+
+    fn execute(&mut self, statement: &Statement) -> Result<Object, Interrupt> {
         statement.accept(self)
     }
 
-    fn evaluate(&self, expression: &Expression) -> Result<Object, Error> {
+    fn evaluate(&mut self, expression: &Expression) -> Result<Object, Interrupt> {
         expression.accept(self)
     }
 
-    fn error(&self, message: &str, line: usize) -> Error {
-        Error::new(ErrorType::RuntimeError, message, line)
+    fn execute_block(
+        &mut self,
+        statements: &[Statement],
+        environment: Environment,
+    ) -> Result<Object, Interrupt> {
+        self.scoped(environment, |interpreter| {
+            statements
+                .iter()
+                .try_for_each(|statement| interpreter.execute(statement).map(|_| ()))
+                .map(|_| Object::Nil)
+        })
     }
-}
 
-impl StatementVisitor<Object> for Interpreter {
-    fn visit_expression_statement(&self, statement: &ExpressionStatement) -> Result<Object, Error> {
-        self.evaluate(&statement.expression)
+    fn scoped<T>(
+        &mut self,
+        environment: Environment,
+        body: impl FnOnce(&mut Self) -> Result<T, Interrupt>,
+    ) -> Result<T, Interrupt> {
+        let previous = mem::replace(&mut self.environment, Rc::new(RefCell::new(environment)));
+        let result = body(self);
+        self.environment = previous;
+        result
     }
 
-    fn visit_let_statement(&mut self, statement: &LetStatement) -> Result<Object, Error> {
-        let value = self.evaluate(&statement.value)?;
-        self.environment.set(&statement.identifier, value);
-        Ok(Object::Nil)
+    // Checks `value` against `pattern`, defining any bindings it makes in
+    // `environment` along the way.
+    fn matches(
+        &self,
+        pattern: &Pattern,
+        value: &Object,
+        environment: &mut Environment,
+    ) -> Result<bool, Interrupt> {
+        match pattern {
+            Pattern::Wildcard => Ok(true),
+            Pattern::Binding(identifier) => {
+                environment.define(identifier, value.clone());
+                Ok(true)
+            }
+            Pattern::Literal(literal) => Ok(value == literal),
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+            } => Ok(match (value, start, end) {
+                (Object::String(x), Object::String(start), Object::String(end)) => {
+                    start <= x && (x < end || *inclusive && x == end)
+                }
+                _ => {
+                    let above = value.compare_numbers(start).is_some_and(Ordering::is_ge);
+                    let below = value
+                        .compare_numbers(end)
+                        .is_some_and(|ordering| ordering.is_lt() || *inclusive && ordering.is_eq());
+                    above && below
+                }
+            }),
+            Pattern::Variant {
+                enumeration,
+                variant,
+                fields,
+            } => {
+                let definition = match self.environment.borrow().get(enumeration)? {
+                    Object::Enum(definition) => definition,
+                    object => {
+                        return Err(self.error(
+                            &format!("Expected an enum before '::', found '{}'", object),
+                            enumeration.line,
+                        ))
+                    }
+                };
+                let Some(index) = definition.variant(&variant.lexeme) else {
+                    return Err(self.error(
+                        &format!(
+                            "No variant named '{}' in '{}'",
+                            variant.lexeme, enumeration.lexeme
+                        ),
+                        variant.line,
+                    ));
+                };
+                let expected = definition.variants[index].fields.as_ref().map(Vec::len);
+                let found = fields.as_ref().map(Vec::len);
+                if expected != found {
+                    return Err(self.error(
+                        &format!(
+                            "Variant '{}::{}' has {} field(s), but the pattern has {}",
+                            enumeration.lexeme,
+                            variant.lexeme,
+                            expected.unwrap_or(0),
+                            found.unwrap_or(0)
+                        ),
+                        variant.line,
+                    ));
+                }
+                match value {
+                    Object::EnumValue(value)
+                        if Rc::ptr_eq(&value.definition, &definition) && value.variant == index =>
+                    {
+                        let fields = fields.as_deref().unwrap_or_default();
+                        self.matches_all(fields, &value.values, environment)
+                    }
+                    _ => Ok(false),
+                }
+            }
+            Pattern::Tuple(patterns) => match value {
+                Object::Tuple(values) if values.len() == patterns.len() => {
+                    self.matches_all(patterns, values, environment)
+                }
+                _ => Ok(false),
+            },
+            Pattern::Array { head, rest, tail } => {
+                let Object::Array(array) = value else {
+                    return Ok(false);
+                };
+                let values = array.borrow().clone();
+                let length = head.len() + tail.len();
+                if values.len() < length || rest.is_none() && values.len() != length {
+                    return Ok(false);
+                }
+                let middle = head.len()..values.len() - tail.len();
+                if !self.matches_all(head, &values[..middle.start], environment)?
+                    || !self.matches_all(tail, &values[middle.end..], environment)?
+                {
+                    return Ok(false);
+                }
+                match rest {
+                    Some(rest) => {
+                        let rest_values = values[middle].to_vec();
+                        let rest_values = Object::Array(Rc::new(RefCell::new(rest_values)));
+                        self.matches(rest, &rest_values, environment)
+                    }
+                    None => Ok(true),
+                }
+            }
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    if self.matches(alternative, value, environment)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+        }
     }
 
-    fn visit_assignment_statement(
+    fn iterate(
+        &self,
+        iterable: Object,
+        line: usize,
+    ) -> Result<Box<dyn Iterator<Item = Object>>, Interrupt> {
+        match iterable {
+            Object::Range {
+                start,
+                end,
+                inclusive: false,
+            } => Ok(Box::new((start..end).map(Object::Integer))),
+            Object::Range {
+                start,
+                end,
+                inclusive: true,
+            } => Ok(Box::new((start..=end).map(Object::Integer))),
+            Object::Array(array) => Ok(Box::new(array.borrow().clone().into_iter())),
+            Object::Map(map) => {
+                let keys: Vec<Object> = map.borrow().keys().map(MapKey::to_object).collect();
+                Ok(Box::new(keys.into_iter()))
+            }
+            Object::String(string) => {
+                let characters: Vec<Object> = string
+                    .chars()
+                    .map(|character| Object::String(character.to_string()))
+                    .collect();
+                Ok(Box::new(characters.into_iter()))
+            }
+            _ => Err(self.error(&format!("Can not iterate over '{}'", iterable), line)),
+        }
+    }
+
+    fn call(
         &mut self,
-        statement: &AssignmentExpression,
-    ) -> Result<Object, Error> {
-        let value = self.evaluate(&statement.value)?;
-        match self.environment.get(&statement.identifier) {
-            Ok(_) => {
-                self.environment.set(&statement.identifier, value);
-                Ok(Object::Nil)
+        callee: Object,
+        arguments: Vec<Object>,
+        paren: &Token,
+    ) -> Result<Object, Interrupt> {
+        match callee {
+            Object::Function(function) => {
+                if arguments.len() != function.arity() {
+                    return Err(self.error(
+                        &format!(
+                            "Function '{}' expected {} argument(s), found {}",
+                            function.identifier.lexeme,
+                            function.arity(),
+                            arguments.len()
+                        ),
+                        paren.line,
+                    ));
+                }
+                self.call_body(
+                    &function.parameters,
+                    &function.body,
+                    &function.closure,
+                    arguments,
+                    paren.line,
+                )
+            }
+            Object::Closure(closure) => {
+                if arguments.len() != closure.arity() {
+                    return Err(self.error(
+                        &format!(
+                            "Closure expected {} argument(s), found {}",
+                            closure.arity(),
+                            arguments.len()
+                        ),
+                        paren.line,
+                    ));
+                }
+                self.call_body(
+                    &closure.parameters,
+                    &closure.body,
+                    &closure.environment,
+                    arguments,
+                    paren.line,
+                )
+            }
+            Object::BoundMethod(bound) => {
+                if arguments.len() != bound.arity() {
+                    return Err(self.error(
+                        &format!(
+                            "Method '{}' expected {} argument(s), found {}",
+                            bound.method.identifier.lexeme,
+                            bound.arity(),
+                            arguments.len()
+                        ),
+                        paren.line,
+                    ));
+                }
+                let mut arguments = arguments;
+                arguments.insert(0, bound.receiver.clone());
+                self.call_body(
+                    &bound.method.parameters,
+                    &bound.method.body,
+                    &bound.method.closure,
+                    arguments,
+                    paren.line,
+                )
+            }
+            Object::VariantConstructor(constructor) => {
+                if arguments.len() != constructor.arity() {
+                    return Err(self.error(
+                        &format!(
+                            "Variant '{}::{}' expected {} argument(s), found {}",
+                            constructor.definition.identifier.lexeme,
+                            constructor.definition.variants[constructor.variant]
+                                .identifier
+                                .lexeme,
+                            constructor.arity(),
+                            arguments.len()
+                        ),
+                        paren.line,
+                    ));
+                }
+                let value = EnumValue::new(
+                    Rc::clone(&constructor.definition),
+                    constructor.variant,
+                    arguments,
+                );
+                Ok(Object::EnumValue(Rc::new(value)))
             }
+            Object::NativeFunction(function) => {
+                if arguments.len() != function.arity {
+                    return Err(self.error(
+                        &format!(
+                            "Function '{}' expected {} argument(s), found {}",
+                            function.name,
+                            function.arity,
+                            arguments.len()
+                        ),
+                        paren.line,
+                    ));
+                }
+                (function.function)(arguments).map_err(|message| {
+                    Interrupt::Error(Error::new(function.raises, &message, paren.line))
+                })
+            }
+            _ => Err(self.error(
+                &format!("Can only call functions, found '{}'", callee),
+                paren.line,
+            )),
+        }
+    }
+
+    fn call_body(
+        &mut self,
+        parameters: &[Token],
+        body: &[Statement],
+        enclosing: &Rc<RefCell<Environment>>,
+        arguments: Vec<Object>,
+        line: usize,
+    ) -> Result<Object, Interrupt> {
+        if self.depth >= MAX_CALL_DEPTH {
+            return Err(self.error(
+                &format!("Maximum call depth of {} exceeded", MAX_CALL_DEPTH),
+                line,
+            ));
+        }
+        let mut environment = Environment::with_parent(Rc::clone(enclosing));
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            environment.define_mutable(parameter, argument);
+        }
+        self.depth += 1;
+        let result = self.execute_block(body, environment);
+        self.depth -= 1;
+        match result {
+            Ok(_) => Ok(Object::Nil),
+            Err(Interrupt::Return(value)) => Ok(value),
+            Err(Interrupt::Error(error)) => Err(Interrupt::Error(in_file(error, enclosing))),
             Err(error) => Err(error),
         }
     }
 
-    fn visit_block_statement(&mut self, statement: &BlockStatement) -> Result<Object, Error> {
-        for statement in &statement.statements {
-            self.execute(&statement)?;
+    fn matches_all(
+        &self,
+        patterns: &[Pattern],
+        values: &[Object],
+        environment: &mut Environment,
+    ) -> Result<bool, Interrupt> {
+        for (pattern, value) in patterns.iter().zip(values) {
+            if !self.matches(pattern, value, environment)? {
+                return Ok(false);
+            }
         }
-        Ok(Object::Nil)
+        Ok(true)
     }
 
-    fn visit_if_statement(&mut self, statement: &IfStatement) -> Result<Object, Error> {
-        let conditional = self.evaluate(&statement.conditional)?;
-        if conditional.is_truthy() {
-            self.execute(&statement.if_block)?;
-        } else {
-            if let Some(else_block) = &statement.else_block {
-                self.execute(else_block)?;
+    // Matches an irrefutable use of a pattern, as in `let (q, r) = ...;`,
+    // and returns the values of its bindings in order.
+    fn destructure<'a>(
+        &self,
+        pattern: &'a Pattern,
+        value: &Object,
+        line: usize,
+    ) -> Result<Vec<(&'a Token, Object)>, Interrupt> {
+        let mut environment = Environment::new();
+        if !self.matches(pattern, value, &mut environment)? {
+            return Err(self.error(
+                &format!("Can not destructure '{}' with this pattern", value),
+                line,
+            ));
+        }
+        let mut bindings = Vec::new();
+        for identifier in pattern.bindings() {
+            bindings.push((identifier, environment.get(identifier)?));
+        }
+        Ok(bindings)
+    }
+
+    fn array_index(&self, index: &Object, length: usize, line: usize) -> Result<usize, Interrupt> {
+        match index {
+            Object::Integer(integer) if *integer < 0 => Err(self.error(
+                &format!("Negative index '{}' is not allowed", integer),
+                line,
+            )),
+            Object::Integer(integer) if *integer as usize >= length => Err(self.error(
+                &format!("Index '{}' is out of range for length {}", integer, length),
+                line,
+            )),
+            Object::Integer(integer) => Ok(*integer as usize),
+            Object::BigInt(integer) => Err(self.error(
+                &format!("Index '{}' is out of range for length {}", integer, length),
+                line,
+            )),
+            _ => Err(self.error(
+                &format!("Expected an integer index, found '{}'", index),
+                line,
+            )),
+        }
+    }
+
+    fn map_key(&self, key: &Object, line: usize) -> Result<MapKey, Interrupt> {
+        MapKey::from_object(key).ok_or_else(|| {
+            self.error(
+                &format!(
+                    "Expected string, number or boolean as map key, found '{}'",
+                    key
+                ),
+                line,
+            )
+        })
+    }
+
+    fn binary(&self, left: Object, operator: &Token, right: Object) -> Result<Object, Interrupt> {
+        match &operator.ttype {
+            TokenType::Plus => match (&left, &right) {
+                (Object::String(x), Object::String(y)) => Ok(Object::String(x.to_string() + y)),
+                (_, _) => arithmetic(&left, &right, i64::checked_add, |x, y| x + y, |x, y| x + y)
+                    .ok_or_else(|| {
+                        self.error(
+                            &format!(
+                                "Expected 'number/string + number/string', found '{} + {}'",
+                                left, right
+                            ),
+                            operator.line,
+                        )
+                    }),
+            },
+            TokenType::Minus => {
+                arithmetic(&left, &right, i64::checked_sub, |x, y| x - y, |x, y| x - y).ok_or_else(
+                    || {
+                        self.error(
+                            &format!("Expected 'number - number', found '{} - {}'", left, right),
+                            operator.line,
+                        )
+                    },
+                )
+            }
+            TokenType::Multiplication => {
+                arithmetic(&left, &right, i64::checked_mul, |x, y| x * y, |x, y| x * y).ok_or_else(
+                    || {
+                        self.error(
+                            &format!("Expected 'number * number', found '{} * {}'", left, right),
+                            operator.line,
+                        )
+                    },
+                )
+            }
+            // Dividing always gives a float, even for two integers.
+            TokenType::Division => match (&left, &right) {
+                (Object::Integer(_) | Object::BigInt(_), Object::Integer(0)) => {
+                    Err(self.error("Division by zero", operator.line))
+                }
+                (_, _) => match (left.as_float(), right.as_float()) {
+                    (Some(x), Some(y)) => Ok(Object::Float(x / y)),
+                    (_, _) => Err(self.error(
+                        &format!("Expected 'number / number', found '{} / {}'", left, right),
+                        operator.line,
+                    )),
+                },
+            },
+            TokenType::Modulo => match (&left, &right) {
+                (Object::Integer(_) | Object::BigInt(_), Object::Integer(0)) => {
+                    Err(self.error("Division by zero", operator.line))
+                }
+                (_, _) => arithmetic(&left, &right, i64::checked_rem, |x, y| x % y, |x, y| x % y)
+                    .ok_or_else(|| {
+                        self.error(
+                            &format!("Expected 'number % number', found '{} % {}'", left, right),
+                            operator.line,
+                        )
+                    }),
+            },
+
+            TokenType::FloorDivision => match (&left, &right) {
+                (Object::Integer(_) | Object::BigInt(_), Object::Integer(0)) => {
+                    Err(self.error("Division by zero", operator.line))
+                }
+                (_, _) => arithmetic(
+                    &left,
+                    &right,
+                    floor_divide,
+                    |x, y| x.div_floor(y).unwrap(),
+                    |x, y| (x / y).floor(),
+                )
+                .ok_or_else(|| {
+                    self.error(
+                        &format!("Expected 'number ~/ number', found '{} ~/ {}'", left, right),
+                        operator.line,
+                    )
+                }),
+            },
+
+            TokenType::Power => self.power(&left, &right, operator),
+
+            TokenType::In => Ok(Object::Boolean(self.contains(&right, &left, operator)?)),
+
+            TokenType::NotIn => Ok(Object::Boolean(!self.contains(&right, &left, operator)?)),
+
+            TokenType::BitwiseAnd => {
+                bitwise(&left, &right, |x, y| x & y, |x, y| x & y).ok_or_else(|| {
+                    self.error(
+                        &format!("Expected 'integer & integer', found '{} & {}'", left, right),
+                        operator.line,
+                    )
+                })
+            }
+
+            TokenType::BitwiseOr => {
+                bitwise(&left, &right, |x, y| x | y, |x, y| x | y).ok_or_else(|| {
+                    self.error(
+                        &format!("Expected 'integer | integer', found '{} | {}'", left, right),
+                        operator.line,
+                    )
+                })
+            }
+
+            TokenType::BitwiseXor => {
+                bitwise(&left, &right, |x, y| x ^ y, |x, y| x ^ y).ok_or_else(|| {
+                    self.error(
+                        &format!("Expected 'integer ^ integer', found '{} ^ {}'", left, right),
+                        operator.line,
+                    )
+                })
+            }
+
+            TokenType::ShiftLeft | TokenType::ShiftRight => {
+                let amount = match (&left, &right) {
+                    (Object::Integer(_) | Object::BigInt(_), Object::Integer(amount)) => *amount,
+                    (_, _) => {
+                        return Err(self.error(
+                            &format!(
+                                "Expected 'integer {} integer', found '{} {} {}'",
+                                operator.lexeme, left, operator.lexeme, right
+                            ),
+                            operator.line,
+                        ))
+                    }
+                };
+                if amount < 0 {
+                    return Err(self.error(
+                        &format!("Can not shift by a negative amount, found '{}'", amount),
+                        operator.line,
+                    ));
+                }
+                if amount > MAX_SHIFT {
+                    return Err(self.error(
+                        &format!("Shift amount '{}' is too large", amount),
+                        operator.line,
+                    ));
+                }
+                Ok(shift(
+                    &left,
+                    amount as usize,
+                    operator.ttype == TokenType::ShiftLeft,
+                ))
+            }
+
+            TokenType::EqualEqual => Ok(Object::Boolean(left == right)),
+
+            TokenType::BangEqual => Ok(Object::Boolean(left != right)),
+
+            TokenType::Greater => match (left.as_float(), right.as_float()) {
+                (Some(_), Some(_)) => Ok(Object::Boolean(
+                    left.compare_numbers(&right).is_some_and(Ordering::is_gt),
+                )),
+                (_, _) => Err(self.error(
+                    &format!("Expected 'number > number found '{} > {}'", left, right),
+                    operator.line,
+                )),
+            },
+
+            TokenType::GreaterEqual => match (left.as_float(), right.as_float()) {
+                (Some(_), Some(_)) => Ok(Object::Boolean(
+                    left.compare_numbers(&right).is_some_and(Ordering::is_ge),
+                )),
+                (_, _) => Err(self.error(
+                    &format!("Expected 'number >= number', found '{} >= {}'", left, right),
+                    operator.line,
+                )),
+            },
+
+            TokenType::Less => match (left.as_float(), right.as_float()) {
+                (Some(_), Some(_)) => Ok(Object::Boolean(
+                    left.compare_numbers(&right).is_some_and(Ordering::is_lt),
+                )),
+                (_, _) => Err(self.error(
+                    &format!("Expected 'number < number', found '{} < {}'", left, right),
+                    operator.line,
+                )),
+            },
+
+            TokenType::LessEqual => match (left.as_float(), right.as_float()) {
+                (Some(_), Some(_)) => Ok(Object::Boolean(
+                    left.compare_numbers(&right).is_some_and(Ordering::is_le),
+                )),
+                (_, _) => Err(self.error(
+                    &format!("Expected 'number <= number', found '{} <= {}'", left, right),
+                    operator.line,
+                )),
+            },
+
+            _ => Err(self.error(
+                &format!("Expected a Binary Operator, found '{}'", operator.lexeme),
+                operator.line,
+            )),
+        }
+    }
+
+    // An integer raised to a non-negative integer stays an integer, growing
+    // into a big integer when it overflows. Anything else gives a float.
+    fn power(
+        &self,
+        base: &Object,
+        exponent: &Object,
+        operator: &Token,
+    ) -> Result<Object, Interrupt> {
+        match (base, exponent) {
+            (Object::Integer(_) | Object::BigInt(_), Object::Integer(exponent))
+                if *exponent >= 0 =>
+            {
+                // Only 0, 1 and -1 can be raised to a huge power, and their
+                // result does not depend on more than the exponent's parity.
+                if *exponent > MAX_EXPONENT {
+                    return match base {
+                        Object::Integer(-1) if exponent % 2 == 0 => Ok(Object::Integer(1)),
+                        Object::Integer(x @ -1..=1) => Ok(Object::Integer(*x)),
+                        _ => Err(self.error(
+                            &format!("Exponent '{}' is too large", exponent),
+                            operator.line,
+                        )),
+                    };
+                }
+                let exponent = *exponent as u32;
+                Ok(match base {
+                    Object::Integer(x) => match x.checked_pow(exponent) {
+                        Some(result) => Object::Integer(result),
+                        None => Object::integer(BigInt::from(*x).pow(exponent)),
+                    },
+                    _ => Object::integer(base.as_big_int().unwrap().pow(exponent)),
+                })
+            }
+            (_, _) => match (base.as_float(), exponent.as_float()) {
+                (Some(x), Some(y)) => Ok(Object::Float(x.powf(y))),
+                (_, _) => Err(self.error(
+                    &format!(
+                        "Expected 'number ** number', found '{} ** {}'",
+                        base, exponent
+                    ),
+                    operator.line,
+                )),
+            },
+        }
+    }
+
+    // Backs 'in' and 'not in': substrings of a string, elements of an array
+    // or tuple, keys of a map and numbers within a range.
+    fn contains(
+        &self,
+        container: &Object,
+        value: &Object,
+        operator: &Token,
+    ) -> Result<bool, Interrupt> {
+        match (container, value) {
+            (Object::String(string), Object::String(part)) => Ok(string.contains(part.as_str())),
+            (Object::Array(array), _) => Ok(array.borrow().contains(value)),
+            (Object::Tuple(elements), _) => Ok(elements.contains(value)),
+            (Object::Map(map), _) => Ok(MapKey::from_object(value)
+                .is_some_and(|key| map.borrow().contains_key(&key))),
+            (
+                Object::Range {
+                    start,
+                    end,
+                    inclusive,
+                },
+                _,
+            ) if value.as_float().is_some() => {
+                let after_start = value
+                    .compare_numbers(&Object::Integer(*start))
+                    .is_some_and(Ordering::is_ge);
+                let before_end = value
+                    .compare_numbers(&Object::Integer(*end))
+                    .is_some_and(|ordering| ordering.is_lt() || *inclusive && ordering.is_eq());
+                Ok(after_start && before_end)
             }
+            (_, _) => Err(self.error(
+                &format!(
+                    "Expected 'string {0} string' or 'value {0} array/tuple/map/range', found '{1} {0} {2}'",
+                    operator.lexeme, value, container
+                ),
+                operator.line,
+            )),
+        }
+    }
+
+    fn index(&self, object: &Object, index: &Object, bracket: &Token) -> Result<Object, Interrupt> {
+        match object {
+            Object::Array(array) => {
+                let array = array.borrow();
+                let index = self.array_index(index, array.len(), bracket.line)?;
+                Ok(array[index].clone())
+            }
+            Object::String(string) => {
+                let characters: Vec<char> = string.chars().collect();
+                let index = self.array_index(index, characters.len(), bracket.line)?;
+                Ok(Object::String(characters[index].to_string()))
+            }
+            Object::Map(map) => {
+                let key = self.map_key(index, bracket.line)?;
+                match map.borrow().get(&key) {
+                    Some(value) => Ok(value.clone()),
+                    None => {
+                        Err(self.error(&format!("Key '{}' not found in map", key), bracket.line))
+                    }
+                }
+            }
+            _ => Err(self.error(&format!("Can not index into '{}'", object), bracket.line)),
+        }
+    }
+
+    fn get(&self, object: &Object, identifier: &Token) -> Result<Object, Interrupt> {
+        let name = &identifier.lexeme;
+        if identifier.ttype == TokenType::Number {
+            return match object {
+                Object::Tuple(elements) => name
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| elements.get(index).cloned())
+                    .ok_or_else(|| {
+                        self.error(
+                            &format!("Tuple '{}' has no element '{}'", object, name),
+                            identifier.line,
+                        )
+                    }),
+                _ => Err(self.error(
+                    &format!("Expected a tuple before '.{}', found '{}'", name, object),
+                    identifier.line,
+                )),
+            };
+        }
+        let (type_name, method) = match object {
+            Object::Error(error) => {
+                return match name.as_str() {
+                    "message" => Ok(Object::String(error.message().to_string())),
+                    "kind" => Ok(Object::String(error.kind().to_string())),
+                    "line" => Ok(Object::Integer(error.line() as i64)),
+                    "file" => Ok(error
+                        .file()
+                        .map_or(Object::Nil, |file| Object::String(file.to_string()))),
+                    _ => Err(self.error(
+                        &format!(
+                            "Errors only have 'message', 'kind', 'line' and 'file', found '{}'",
+                            name
+                        ),
+                        identifier.line,
+                    )),
+                };
+            }
+            Object::Instance(instance) => {
+                let instance = instance.borrow();
+                if let Some(value) = instance.get(name) {
+                    return Ok(value);
+                }
+                (
+                    instance.definition.identifier.lexeme.clone(),
+                    instance.definition.method(name),
+                )
+            }
+            Object::EnumValue(value) => (
+                value.definition.identifier.lexeme.clone(),
+                value.definition.method(name),
+            ),
+            _ => {
+                return Err(self.error(
+                    &format!(
+                        "Only struct instances and enum values have fields or methods, found '{}'",
+                        object
+                    ),
+                    identifier.line,
+                ))
+            }
+        };
+        match method {
+            Some(method) if method.is_method() => Ok(Object::BoundMethod(Rc::new(
+                BoundMethod::new(object.clone(), method),
+            ))),
+            Some(_) => Err(self.error(
+                &format!(
+                    "'{}' is an associated function, call it as '{}::{}'",
+                    name, type_name, name
+                ),
+                identifier.line,
+            )),
+            None => Err(self.error(
+                &format!("'{}' has no field or method named '{}'", type_name, name),
+                identifier.line,
+            )),
+        }
+    }
+
+    // The value to store for an assignment, where `current` reads the old
+    // value and is only called for compound operators like `+=`.
+    fn assigned_value(
+        &mut self,
+        operator: &Option<Token>,
+        value: &Expression,
+        current: impl FnOnce(&Self) -> Result<Object, Interrupt>,
+    ) -> Result<Object, Interrupt> {
+        match operator {
+            Some(operator) => {
+                let current = current(self)?;
+                let value = self.evaluate(value)?;
+                self.binary(current, operator, value)
+            }
+            None => self.evaluate(value),
+        }
+    }
+
+    fn error(&self, message: &str, line: usize) -> Interrupt {
+        Interrupt::Error(Error::new(ErrorType::RuntimeError, message, line))
+    }
+}
+
+// Unlabeled 'break'/'continue' target the innermost loop, labeled ones only
+// the loop carrying the same label.
+fn targets(label: &Option<String>, loop_label: &Option<Token>) -> bool {
+    match (label, loop_label) {
+        (None, _) => true,
+        (Some(label), Some(loop_label)) => *label == loop_label.lexeme,
+        (Some(_), None) => false,
+    }
+}
+
+// Integers stay integers, switching to a big integer when an operation
+// overflows, and any float operand makes the result a float. Gives None if
+// either operand is no number.
+fn arithmetic(
+    left: &Object,
+    right: &Object,
+    integer: fn(i64, i64) -> Option<i64>,
+    big: fn(&BigInt, &BigInt) -> BigInt,
+    float: fn(f64, f64) -> f64,
+) -> Option<Object> {
+    match (left, right) {
+        (Object::Integer(x), Object::Integer(y)) => Some(match integer(*x, *y) {
+            Some(result) => Object::Integer(result),
+            None => Object::integer(big(&BigInt::from(*x), &BigInt::from(*y))),
+        }),
+        (Object::Integer(_) | Object::BigInt(_), Object::Integer(_) | Object::BigInt(_)) => Some(
+            Object::integer(big(&left.as_big_int()?, &right.as_big_int()?)),
+        ),
+        _ => Some(Object::Float(float(left.as_float()?, right.as_float()?))),
+    }
+}
+
+// Names the file whose code runs in `environment` as the source of `error`.
+fn in_file(error: Error, environment: &Rc<RefCell<Environment>>) -> Error {
+    match environment.borrow().file() {
+        Some(file) => error.in_file(&file),
+        None => error,
+    }
+}
+
+// A fresh global environment, holding only the builtins.
+fn globals(mut environment: Environment) -> Environment {
+    for builtin in builtins() {
+        environment.define_builtin(builtin.name, Object::NativeFunction(Rc::new(builtin)));
+    }
+    environment
+}
+
+// Rounds towards negative infinity, unlike `/` on i64. Gives None on
+// overflow.
+fn floor_divide(x: i64, y: i64) -> Option<i64> {
+    let quotient = x.checked_div(y)?;
+    if x % y != 0 && (x < 0) != (y < 0) {
+        Some(quotient - 1)
+    } else {
+        Some(quotient)
+    }
+}
+
+// Bitwise operators only take integers, which behave as if stored in two's
+// complement no matter how large they get. Gives None for anything else.
+fn bitwise(
+    left: &Object,
+    right: &Object,
+    integer: fn(i64, i64) -> i64,
+    big: fn(&BigInt, &BigInt) -> BigInt,
+) -> Option<Object> {
+    match (left, right) {
+        (Object::Integer(x), Object::Integer(y)) => Some(Object::Integer(integer(*x, *y))),
+        _ => Some(Object::integer(big(
+            &left.as_big_int()?,
+            &right.as_big_int()?,
+        ))),
+    }
+}
+
+// Shifting left never overflows, it switches to a big integer instead.
+// `value` must be an integer.
+fn shift(value: &Object, amount: usize, left: bool) -> Object {
+    match (value, left) {
+        (Object::Integer(x), true) if amount < 63 => match x.checked_mul(1 << amount) {
+            Some(shifted) => Object::Integer(shifted),
+            None => Object::integer(&BigInt::from(*x) << amount),
+        },
+        (Object::Integer(x), false) => Object::Integer(x >> amount.min(63)),
+        (_, true) => Object::integer(&value.as_big_int().unwrap() << amount),
+        (_, false) => Object::integer(&value.as_big_int().unwrap() >> amount),
+    }
+}
+
+impl StatementVisitor<Object> for Interpreter {
+    fn visit_expression_statement(
+        &mut self,
+        statement: &ExpressionStatement,
+    ) -> Result<Object, Interrupt> {
+        self.evaluate(&statement.expression)
+    }
+
+    fn visit_let_statement(&mut self, statement: &LetStatement) -> Result<Object, Interrupt> {
+        let value = self.evaluate(&statement.value)?;
+        let bindings = self.destructure(&statement.pattern, &value, statement.keyword.line)?;
+        let mut environment = self.environment.borrow_mut();
+        for (identifier, value) in bindings {
+            if statement.mutable {
+                environment.define_mutable(identifier, value);
+            } else {
+                environment.define(identifier, value);
+            }
+        }
+        Ok(Object::Nil)
+    }
+
+    fn visit_use_statement(&mut self, statement: &UseStatement) -> Result<Object, Interrupt> {
+        let module = self.load(&statement.path, &statement.keyword)?;
+        let mut environment = self.environment.borrow_mut();
+        if let Some(identifier) = &statement.module {
+            environment.define(identifier, Object::Module(Rc::clone(&module)));
+        }
+        for item in &statement.items {
+            let value = module.get(&item.lexeme).ok_or_else(|| {
+                self.error(
+                    &format!(
+                        "Module '{}' has no public item named '{}'",
+                        module.name, item.lexeme
+                    ),
+                    item.line,
+                )
+            })?;
+            environment.define(item, value);
         }
         Ok(Object::Nil)
     }
 
-    fn visit_while_statement(&mut self, statement: &WhileStatement) -> Result<Object, Error> {
+    fn visit_const_statement(&mut self, statement: &ConstStatement) -> Result<Object, Interrupt> {
+        let value = self.evaluate(&statement.value)?;
+        self.environment
+            .borrow_mut()
+            .define(&statement.identifier, value);
+        Ok(Object::Nil)
+    }
+
+    fn visit_while_statement(&mut self, statement: &WhileStatement) -> Result<Object, Interrupt> {
         loop {
             let conditional = self.evaluate(&statement.conditional)?;
-            if conditional.is_truthy() {
-                self.execute(&statement.block)?;
-            } else {
+            if !conditional.is_truthy() {
                 break;
             }
+            match statement.block.accept(self) {
+                Err(Interrupt::Break(label, _)) if targets(&label, &statement.label) => break,
+                Err(Interrupt::Continue(label)) if targets(&label, &statement.label) => continue,
+                result => result?,
+            };
+        }
+        Ok(Object::Nil)
+    }
+
+    fn visit_for_statement(&mut self, statement: &ForStatement) -> Result<Object, Interrupt> {
+        let iterable = self.evaluate(&statement.iterable)?;
+        for item in self.iterate(iterable, statement.variable.line)? {
+            let mut environment = Environment::with_parent(Rc::clone(&self.environment));
+            environment.define(&statement.variable, item);
+            match self.scoped(environment, |interpreter| {
+                statement.block.accept(interpreter)
+            }) {
+                Err(Interrupt::Break(label, _)) if targets(&label, &statement.label) => break,
+                Err(Interrupt::Continue(label)) if targets(&label, &statement.label) => continue,
+                result => result?,
+            };
         }
         Ok(Object::Nil)
     }
 
-    fn visit_print_statement(&self, statement: &PrintStatement) -> Result<Object, Error> {
+    fn visit_print_statement(&mut self, statement: &PrintStatement) -> Result<Object, Interrupt> {
         let mut values = Vec::new();
         for value in &statement.values {
             values.push(self.evaluate(value)?);
@@ -113,22 +1146,129 @@ impl StatementVisitor<Object> for Interpreter {
         println!();
         Ok(Object::Nil)
     }
+
+    fn visit_function_statement(
+        &mut self,
+        statement: &FunctionStatement,
+    ) -> Result<Object, Interrupt> {
+        let function = Function::new(
+            statement.identifier.clone(),
+            statement.parameters.clone(),
+            Rc::clone(&statement.body),
+            Rc::clone(&self.environment),
+        );
+        self.environment
+            .borrow_mut()
+            .define(&statement.identifier, Object::Function(Rc::new(function)));
+        Ok(Object::Nil)
+    }
+
+    fn visit_break_statement(&mut self, statement: &BreakStatement) -> Result<Object, Interrupt> {
+        let label = statement.label.as_ref().map(|label| label.lexeme.clone());
+        let value = match &statement.value {
+            Some(value) => self.evaluate(value)?,
+            None => Object::Nil,
+        };
+        Err(Interrupt::Break(label, value))
+    }
+
+    fn visit_continue_statement(
+        &mut self,
+        statement: &ContinueStatement,
+    ) -> Result<Object, Interrupt> {
+        let label = statement.label.as_ref().map(|label| label.lexeme.clone());
+        Err(Interrupt::Continue(label))
+    }
+
+    fn visit_struct_statement(&mut self, statement: &StructStatement) -> Result<Object, Interrupt> {
+        let definition =
+            StructDefinition::new(statement.identifier.clone(), statement.fields.clone());
+        self.environment
+            .borrow_mut()
+            .define(&statement.identifier, Object::Struct(Rc::new(definition)));
+        Ok(Object::Nil)
+    }
+
+    fn visit_enum_statement(&mut self, statement: &EnumStatement) -> Result<Object, Interrupt> {
+        let definition =
+            EnumDefinition::new(statement.identifier.clone(), statement.variants.clone());
+        self.environment
+            .borrow_mut()
+            .define(&statement.identifier, Object::Enum(Rc::new(definition)));
+        Ok(Object::Nil)
+    }
+
+    fn visit_impl_statement(&mut self, statement: &ImplStatement) -> Result<Object, Interrupt> {
+        let target = self.environment.borrow().get(&statement.identifier)?;
+        let methods = match &target {
+            Object::Struct(definition) => &definition.methods,
+            Object::Enum(definition) => &definition.methods,
+            _ => {
+                return Err(self.error(
+                    &format!("Can not implement methods for '{}'", target),
+                    statement.identifier.line,
+                ))
+            }
+        };
+        for method in &statement.methods {
+            let function = Function::new(
+                method.identifier.clone(),
+                method.parameters.clone(),
+                Rc::clone(&method.body),
+                Rc::clone(&self.environment),
+            );
+            let mut methods = methods.borrow_mut();
+            if methods.contains_key(&method.identifier.lexeme) {
+                return Err(self.error(
+                    &format!(
+                        "Method '{}' is already defined for '{}'",
+                        method.identifier.lexeme, statement.identifier.lexeme
+                    ),
+                    method.identifier.line,
+                ));
+            }
+            methods.insert(method.identifier.lexeme.clone(), Rc::new(function));
+        }
+        Ok(Object::Nil)
+    }
+
+    fn visit_return_statement(&mut self, statement: &ReturnStatement) -> Result<Object, Interrupt> {
+        let value = match &statement.value {
+            Some(value) => self.evaluate(value)?,
+            None => Object::Nil,
+        };
+        Err(Interrupt::Return(value))
+    }
 }
 
 impl ExpressionVisitor<Object> for Interpreter {
-    fn visit_variable_expression(&self, expression: &VariableExpression) -> Result<Object, Error> {
-        self.environment.get(&expression.identifier)
+    fn visit_variable_expression(
+        &mut self,
+        expression: &VariableExpression,
+    ) -> Result<Object, Interrupt> {
+        Ok(self.environment.borrow().get(&expression.identifier)?)
     }
 
-    fn visit_literal_expression(&self, expression: &LiteralExpression) -> Result<Object, Error> {
+    fn visit_literal_expression(
+        &mut self,
+        expression: &LiteralExpression,
+    ) -> Result<Object, Interrupt> {
         Ok(expression.literal.clone())
     }
 
-    fn visit_unary_expression(&self, expression: &UnaryExpression) -> Result<Object, Error> {
+    fn visit_unary_expression(
+        &mut self,
+        expression: &UnaryExpression,
+    ) -> Result<Object, Interrupt> {
         let right = self.evaluate(&expression.right)?;
         match &expression.operator.ttype {
             TokenType::Minus => match &right {
-                Object::Number(number) => Ok(Object::Number(number * -1.)),
+                Object::Integer(integer) => Ok(match integer.checked_neg() {
+                    Some(negated) => Object::Integer(negated),
+                    None => Object::integer(-&BigInt::from(*integer)),
+                }),
+                Object::BigInt(integer) => Ok(Object::integer(-integer.as_ref())),
+                Object::Float(float) => Ok(Object::Float(-float)),
                 _ => Err(self.error(
                     &format!("Expected number after '-', found '{}", right),
                     expression.operator.line,
@@ -141,6 +1281,14 @@ impl ExpressionVisitor<Object> for Interpreter {
                     expression.operator.line,
                 )),
             },
+            TokenType::BitwiseNot => match &right {
+                Object::Integer(integer) => Ok(Object::Integer(!integer)),
+                Object::BigInt(integer) => Ok(Object::integer(!integer.as_ref())),
+                _ => Err(self.error(
+                    &format!("Expected integer after '~', found '{}'", right),
+                    expression.operator.line,
+                )),
+            },
             _ => Err(self.error(
                 &format!("Expected '-', found '{}", expression.operator.lexeme),
                 expression.operator.line,
@@ -148,105 +1296,629 @@ impl ExpressionVisitor<Object> for Interpreter {
         }
     }
 
-    fn visit_binary_expression(&self, expression: &BinaryExpression) -> Result<Object, Error> {
+    fn visit_binary_expression(
+        &mut self,
+        expression: &BinaryExpression,
+    ) -> Result<Object, Interrupt> {
         let left = self.evaluate(&expression.left)?;
         let right = self.evaluate(&expression.right)?;
-        match &expression.operator.ttype {
-            TokenType::Plus => match (&left, &right) {
-                (Object::Number(x), Object::Number(y)) => Ok(Object::Number(x + y)),
-                (Object::String(x), Object::String(y)) => Ok(Object::String(x.to_string() + y)),
-                (_, _) => Err(self.error(
-                    &format!(
-                        "Expected 'number/string + number/string', found '{} + {}'",
-                        left, right
-                    ),
-                    expression.operator.line,
-                )),
-            },
-            TokenType::Minus => match (&left, &right) {
-                (Object::Number(x), Object::Number(y)) => Ok(Object::Number(x - y)),
-                (_, _) => Err(self.error(
-                    &format!("Expected 'number - number', found '{} - {}'", left, right),
-                    expression.operator.line,
-                )),
-            },
-            TokenType::Multiplication => match (&left, &right) {
-                (Object::Number(x), Object::Number(y)) => Ok(Object::Number(x * y)),
-                (_, _) => Err(self.error(
-                    &format!("Expected 'number * number', found '{} * {}'", left, right),
-                    expression.operator.line,
-                )),
-            },
-            TokenType::Division => match (&left, &right) {
-                (Object::Number(x), Object::Number(y)) => Ok(Object::Number(x / y)),
-                (_, _) => Err(self.error(
-                    &format!("Expected 'number / number', found '{} / {}'", left, right),
-                    expression.operator.line,
-                )),
-            },
-            TokenType::Modulo => match (&left, &right) {
-                (Object::Number(x), Object::Number(y)) => Ok(Object::Number(x % y)),
-                (_, _) => Err(self.error(
-                    &format!("Expected 'number % number', found '{} % {}'", left, right),
-                    expression.operator.line,
-                )),
-            },
+        self.binary(left, &expression.operator, right)
+    }
 
-            TokenType::BitwiseAnd => todo!(),
+    // Gives back the operand that decided the result rather than a boolean,
+    // so `name || "default"` picks the first truthy value and `a && b`
+    // gives `a` when it is falsy and `b` otherwise.
+    fn visit_logical_expression(
+        &mut self,
+        expression: &LogicalExpression,
+    ) -> Result<Object, Interrupt> {
+        let left = self.evaluate(&expression.left)?;
+        let decided = match expression.operator.ttype {
+            TokenType::Or => left.is_truthy(),
+            _ => !left.is_truthy(),
+        };
+        if decided {
+            Ok(left)
+        } else {
+            self.evaluate(&expression.right)
+        }
+    }
 
-            TokenType::BitwiseOr => todo!(),
+    fn visit_gruping_expression(
+        &mut self,
+        expression: &GroupingExpression,
+    ) -> Result<Object, Interrupt> {
+        self.evaluate(&expression.expressions)
+    }
 
-            TokenType::EqualEqual => Ok(Object::Boolean(left == right)),
+    fn visit_call_expression(&mut self, expression: &CallExpression) -> Result<Object, Interrupt> {
+        let callee = self.evaluate(&expression.callee)?;
+        let mut arguments = Vec::new();
+        for argument in &expression.arguments {
+            arguments.push(self.evaluate(argument)?);
+        }
+        self.call(callee, arguments, &expression.paren)
+    }
 
-            TokenType::BangEqual => Ok(Object::Boolean(left != right)),
+    fn visit_range_expression(
+        &mut self,
+        expression: &RangeExpression,
+    ) -> Result<Object, Interrupt> {
+        let start = self.evaluate(&expression.start)?;
+        let end = self.evaluate(&expression.end)?;
+        match (&start, &end) {
+            (Object::Integer(x), Object::Integer(y)) => Ok(Object::Range {
+                start: *x,
+                end: *y,
+                inclusive: expression.operator.ttype == TokenType::DotDotEqual,
+            }),
+            (_, _) => Err(self.error(
+                &format!(
+                    "Expected 'integer{}integer', found '{}{}{}'",
+                    expression.operator.lexeme, start, expression.operator.lexeme, end
+                ),
+                expression.operator.line,
+            )),
+        }
+    }
 
-            TokenType::Greater => match (&left, &right) {
-                (Object::Number(x), Object::Number(y)) => Ok(Object::Boolean(x > y)),
-                (_, _) => Err(self.error(
-                    &format!("Expected 'number > number found '{} > {}'", left, right),
-                    expression.operator.line,
-                )),
-            },
+    fn visit_tuple_expression(
+        &mut self,
+        expression: &TupleExpression,
+    ) -> Result<Object, Interrupt> {
+        let mut elements = Vec::new();
+        for element in &expression.elements {
+            elements.push(self.evaluate(element)?);
+        }
+        Ok(Object::Tuple(Rc::new(elements)))
+    }
 
-            TokenType::GreaterEqual => match (&left, &right) {
-                (Object::Number(x), Object::Number(y)) => Ok(Object::Boolean(x >= y)),
-                (_, _) => Err(self.error(
-                    &format!("Expected 'number >= number', found '{} >= {}'", left, right),
-                    expression.operator.line,
-                )),
-            },
+    fn visit_array_expression(
+        &mut self,
+        expression: &ArrayExpression,
+    ) -> Result<Object, Interrupt> {
+        let mut elements = Vec::new();
+        for element in &expression.elements {
+            elements.push(self.evaluate(element)?);
+        }
+        Ok(Object::Array(Rc::new(RefCell::new(elements))))
+    }
 
-            TokenType::Less => match (&left, &right) {
-                (Object::Number(x), Object::Number(y)) => Ok(Object::Boolean(x < y)),
-                (_, _) => Err(self.error(
-                    &format!("Expected 'number < number', found '{} < {}'", left, right),
-                    expression.operator.line,
-                )),
-            },
+    fn visit_index_expression(
+        &mut self,
+        expression: &IndexExpression,
+    ) -> Result<Object, Interrupt> {
+        let object = self.evaluate(&expression.object)?;
+        let index = self.evaluate(&expression.index)?;
+        self.index(&object, &index, &expression.bracket)
+    }
 
-            TokenType::LessEqual => match (&left, &right) {
-                (Object::Number(x), Object::Number(y)) => Ok(Object::Boolean(x <= y)),
-                (_, _) => Err(self.error(
-                    &format!("Expected 'number <= number', found '{} <= {}'", left, right),
-                    expression.operator.line,
-                )),
-            },
+    fn visit_map_expression(&mut self, expression: &MapExpression) -> Result<Object, Interrupt> {
+        let mut map = Map::new();
+        for (key, value) in &expression.entries {
+            let key = self.evaluate(key)?;
+            let key = self.map_key(&key, expression.brace.line)?;
+            let value = self.evaluate(value)?;
+            map.insert(key, value);
+        }
+        Ok(Object::Map(Rc::new(RefCell::new(map))))
+    }
 
-            TokenType::And => Ok(Object::Boolean(left.is_truthy() && right.is_truthy())),
+    fn visit_struct_expression(
+        &mut self,
+        expression: &StructExpression,
+    ) -> Result<Object, Interrupt> {
+        let definition = match self.environment.borrow().get(&expression.identifier)? {
+            Object::Struct(definition) => definition,
+            object => {
+                return Err(self.error(
+                    &format!("Expected a struct before '{{', found '{}'", object),
+                    expression.identifier.line,
+                ))
+            }
+        };
+        let mut values = Vec::new();
+        for (field, value) in &expression.fields {
+            if !definition.has_field(&field.lexeme) {
+                return Err(self.error(
+                    &format!(
+                        "Struct '{}' has no field named '{}'",
+                        definition.identifier.lexeme, field.lexeme
+                    ),
+                    field.line,
+                ));
+            }
+            values.push((field.lexeme.clone(), self.evaluate(value)?));
+        }
+        let mut fields = Vec::new();
+        for field in &definition.fields {
+            match values.iter().position(|(name, _)| *name == field.lexeme) {
+                Some(index) => fields.push(values.swap_remove(index)),
+                None => {
+                    return Err(self.error(
+                        &format!(
+                            "Missing field '{}' in initializer of '{}'",
+                            field.lexeme, definition.identifier.lexeme
+                        ),
+                        expression.identifier.line,
+                    ))
+                }
+            }
+        }
+        let instance = Instance::new(definition, fields);
+        Ok(Object::Instance(Rc::new(RefCell::new(instance))))
+    }
 
-            TokenType::Or => Ok(Object::Boolean(left.is_truthy() || right.is_truthy())),
+    fn visit_get_expression(&mut self, expression: &GetExpression) -> Result<Object, Interrupt> {
+        let object = self.evaluate(&expression.object)?;
+        self.get(&object, &expression.identifier)
+    }
 
+    fn visit_assignment_expression(
+        &mut self,
+        expression: &AssignmentExpression,
+    ) -> Result<Object, Interrupt> {
+        let value =
+            self.assigned_value(&expression.operator, &expression.value, |interpreter| {
+                Ok(interpreter
+                    .environment
+                    .borrow()
+                    .get(&expression.identifier)?)
+            })?;
+        self.environment
+            .borrow_mut()
+            .assign(&expression.identifier, value.clone())?;
+        Ok(value)
+    }
+
+    fn visit_index_set_expression(
+        &mut self,
+        expression: &IndexSetExpression,
+    ) -> Result<Object, Interrupt> {
+        let object = self.evaluate(&expression.object)?;
+        let index = self.evaluate(&expression.index)?;
+        let value =
+            self.assigned_value(&expression.operator, &expression.value, |interpreter| {
+                interpreter.index(&object, &index, &expression.bracket)
+            })?;
+        match &object {
+            Object::Array(array) => {
+                let length = array.borrow().len();
+                let index = self.array_index(&index, length, expression.bracket.line)?;
+                array.borrow_mut()[index] = value.clone();
+                Ok(value)
+            }
+            Object::Map(map) => {
+                let key = self.map_key(&index, expression.bracket.line)?;
+                map.borrow_mut().insert(key, value.clone());
+                Ok(value)
+            }
             _ => Err(self.error(
+                &format!("Can not assign to an index of '{}'", object),
+                expression.bracket.line,
+            )),
+        }
+    }
+
+    fn visit_set_expression(&mut self, expression: &SetExpression) -> Result<Object, Interrupt> {
+        let object = self.evaluate(&expression.object)?;
+        let value =
+            self.assigned_value(&expression.operator, &expression.value, |interpreter| {
+                interpreter.get(&object, &expression.identifier)
+            })?;
+        match &object {
+            Object::Instance(instance) => {
+                let mut instance = instance.borrow_mut();
+                if instance.set(&expression.identifier.lexeme, value.clone()) {
+                    Ok(value)
+                } else {
+                    Err(self.error(
+                        &format!(
+                            "Struct '{}' has no field named '{}'",
+                            instance.definition.identifier.lexeme, expression.identifier.lexeme
+                        ),
+                        expression.identifier.line,
+                    ))
+                }
+            }
+            _ => Err(self.error(
+                &format!("Only struct instances have fields, found '{}'", object),
+                expression.identifier.line,
+            )),
+        }
+    }
+
+    fn visit_destructure_expression(
+        &mut self,
+        expression: &DestructureExpression,
+    ) -> Result<Object, Interrupt> {
+        let value = self.evaluate(&expression.value)?;
+        let bindings = self.destructure(&expression.pattern, &value, expression.equal.line)?;
+        for (identifier, value) in bindings {
+            self.environment.borrow_mut().assign(identifier, value)?;
+        }
+        Ok(value)
+    }
+
+    fn visit_path_expression(&mut self, expression: &PathExpression) -> Result<Object, Interrupt> {
+        let object = self.evaluate(&expression.object)?;
+        let name = &expression.identifier.lexeme;
+        let (type_name, method) = match &object {
+            Object::Module(module) => {
+                return module.get(name).ok_or_else(|| {
+                    self.error(
+                        &format!(
+                            "Module '{}' has no public item named '{}'",
+                            module.name, name
+                        ),
+                        expression.identifier.line,
+                    )
+                })
+            }
+            Object::Struct(definition) => (&definition.identifier, definition.method(name)),
+            Object::Enum(definition) => {
+                if let Some(variant) = definition.variant(name) {
+                    return Ok(match definition.variants[variant].fields {
+                        Some(_) => Object::VariantConstructor(Rc::new(VariantConstructor::new(
+                            Rc::clone(definition),
+                            variant,
+                        ))),
+                        None => Object::EnumValue(Rc::new(EnumValue::new(
+                            Rc::clone(definition),
+                            variant,
+                            Vec::new(),
+                        ))),
+                    });
+                }
+                (&definition.identifier, definition.method(name))
+            }
+            _ => {
+                return Err(self.error(
+                    &format!("Expected a type before '::', found '{}'", object),
+                    expression.identifier.line,
+                ))
+            }
+        };
+        method.map(Object::Function).ok_or_else(|| {
+            self.error(
                 &format!(
-                    "Expected a Binary Operator, found '{}'",
-                    expression.operator.lexeme
+                    "No associated item named '{}' in '{}'",
+                    name, type_name.lexeme
                 ),
-                expression.operator.line,
-            )),
+                expression.identifier.line,
+            )
+        })
+    }
+
+    fn visit_block_expression(
+        &mut self,
+        expression: &BlockExpression,
+    ) -> Result<Object, Interrupt> {
+        let environment = Environment::with_parent(Rc::clone(&self.environment));
+        self.scoped(environment, |interpreter| {
+            for statement in &expression.statements {
+                interpreter.execute(statement)?;
+            }
+            match &expression.tail {
+                Some(tail) => interpreter.evaluate(tail),
+                None => Ok(Object::Nil),
+            }
+        })
+    }
+
+    // Without an else branch an 'if' whose condition fails gives nil.
+    fn visit_if_expression(&mut self, expression: &IfExpression) -> Result<Object, Interrupt> {
+        if self.evaluate(&expression.condition)?.is_truthy() {
+            expression.then_branch.accept(self)
+        } else {
+            match &expression.else_branch {
+                Some(else_branch) => self.evaluate(else_branch),
+                None => Ok(Object::Nil),
+            }
         }
     }
 
-    fn visit_gruping_expression(&self, expression: &GroupingExpression) -> Result<Object, Error> {
-        self.evaluate(&expression.expressions)
+    fn visit_loop_expression(&mut self, expression: &LoopExpression) -> Result<Object, Interrupt> {
+        loop {
+            match expression.body.accept(self) {
+                Err(Interrupt::Break(label, value)) if targets(&label, &expression.label) => {
+                    return Ok(value)
+                }
+                Err(Interrupt::Continue(label)) if targets(&label, &expression.label) => continue,
+                result => result?,
+            };
+        }
+    }
+
+    fn visit_try_expression(&mut self, expression: &TryExpression) -> Result<Object, Interrupt> {
+        match expression.body.accept(self) {
+            Err(Interrupt::Error(error)) => {
+                let error = in_file(error, &self.environment);
+                let mut environment = Environment::with_parent(Rc::clone(&self.environment));
+                if let Some(binding) = &expression.binding {
+                    environment.define(binding, Object::Error(Rc::new(error)));
+                }
+                self.scoped(environment, |interpreter| {
+                    expression.handler.accept(interpreter)
+                })
+            }
+            result => result,
+        }
+    }
+
+    fn visit_match_expression(
+        &mut self,
+        expression: &MatchExpression,
+    ) -> Result<Object, Interrupt> {
+        let subject = self.evaluate(&expression.subject)?;
+        for arm in &expression.arms {
+            let mut environment = Environment::with_parent(Rc::clone(&self.environment));
+            if !self.matches(&arm.pattern, &subject, &mut environment)? {
+                continue;
+            }
+            let value = self.scoped(environment, |interpreter| {
+                if let Some(guard) = &arm.guard {
+                    if !interpreter.evaluate(guard)?.is_truthy() {
+                        return Ok(None);
+                    }
+                }
+                interpreter.evaluate(&arm.body).map(Some)
+            })?;
+            if let Some(value) = value {
+                return Ok(value);
+            }
+        }
+        Err(self.error(
+            &format!("No match arm matched '{}'", subject),
+            expression.keyword.line,
+        ))
+    }
+
+    fn visit_lambda_expression(
+        &mut self,
+        expression: &LambdaExpression,
+    ) -> Result<Object, Interrupt> {
+        let closure = Closure::new(
+            expression.parameters.clone(),
+            Rc::clone(&expression.body),
+            Rc::clone(&self.environment),
+        );
+        Ok(Object::Closure(Rc::new(closure)))
+    }
+
+    fn visit_format_expression(
+        &mut self,
+        expression: &FormatExpression,
+    ) -> Result<Object, Interrupt> {
+        let mut string = String::new();
+        for part in &expression.parts {
+            match part {
+                FormatPart::Literal(literal) => string.push_str(literal),
+                FormatPart::Interpolation {
+                    expression,
+                    spec,
+                    brace,
+                } => {
+                    let value = self.evaluate(expression)?;
+                    let formatted = spec
+                        .format(&value)
+                        .map_err(|message| self.error(&message, brace.line))?;
+                    string.push_str(&formatted);
+                }
+            }
+        }
+        Ok(Object::String(string))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(left: Object, ttype: TokenType, lexeme: &str, right: Object) -> Object {
+        let operator = Token::new(ttype, lexeme, Object::Nil, 1);
+        match Interpreter::new().binary(left, &operator, right) {
+            Ok(value) => value,
+            Err(_) => panic!("'{}' failed", lexeme),
+        }
+    }
+
+    fn fails(left: Object, ttype: TokenType, lexeme: &str, right: Object) -> String {
+        let operator = Token::new(ttype, lexeme, Object::Nil, 1);
+        match Interpreter::new().binary(left, &operator, right) {
+            Err(Interrupt::Error(error)) => error.message().to_string(),
+            _ => panic!("'{}' did not fail", lexeme),
+        }
+    }
+
+    fn big(digits: &str) -> Object {
+        Object::BigInt(Rc::new(digits.parse().unwrap()))
+    }
+
+    #[test]
+    fn promotes_overflowing_arithmetic() {
+        let max = Object::Integer(i64::MAX);
+        let min = Object::Integer(i64::MIN);
+        assert_eq!(
+            evaluate(max.clone(), TokenType::Plus, "+", Object::Integer(1)),
+            big("9223372036854775808")
+        );
+        assert_eq!(
+            evaluate(min.clone(), TokenType::Minus, "-", Object::Integer(1)),
+            big("-9223372036854775809")
+        );
+        assert_eq!(
+            evaluate(
+                min.clone(),
+                TokenType::Multiplication,
+                "*",
+                Object::Integer(-1)
+            ),
+            big("9223372036854775808")
+        );
+        assert_eq!(
+            evaluate(max, TokenType::Plus, "+", Object::Float(1.)),
+            Object::Float(i64::MAX as f64 + 1.)
+        );
+    }
+
+    #[test]
+    fn demotes_results_that_fit() {
+        let result = evaluate(
+            big("9223372036854775808"),
+            TokenType::Minus,
+            "-",
+            Object::Integer(1),
+        );
+        assert!(matches!(result, Object::Integer(i64::MAX)));
+        let result = evaluate(
+            big("-9223372036854775808"),
+            TokenType::Modulo,
+            "%",
+            Object::Integer(3),
+        );
+        assert!(matches!(result, Object::Integer(_)));
+    }
+
+    #[test]
+    fn rejects_big_division_by_zero() {
+        assert_eq!(
+            fails(
+                big("9223372036854775808"),
+                TokenType::Modulo,
+                "%",
+                Object::Integer(0)
+            ),
+            "Division by zero"
+        );
+        assert_eq!(
+            fails(
+                big("9223372036854775808"),
+                TokenType::Division,
+                "/",
+                Object::Integer(0)
+            ),
+            "Division by zero"
+        );
+    }
+
+    #[test]
+    fn floor_divides() {
+        assert_eq!(floor_divide(-7, 2), Some(-4));
+        assert_eq!(floor_divide(7, -2), Some(-4));
+        assert_eq!(floor_divide(-6, 2), Some(-3));
+        assert_eq!(floor_divide(7, 2), Some(3));
+        assert_eq!(floor_divide(i64::MIN, -1), None);
+        assert_eq!(
+            evaluate(
+                Object::Integer(i64::MIN),
+                TokenType::FloorDivision,
+                "~/",
+                Object::Integer(-1)
+            ),
+            big("9223372036854775808")
+        );
+        assert_eq!(
+            fails(
+                Object::Integer(1),
+                TokenType::FloorDivision,
+                "~/",
+                Object::Integer(0)
+            ),
+            "Division by zero"
+        );
+    }
+
+    #[test]
+    fn shifts_up_to_max_shift() {
+        assert_eq!(
+            shift(&Object::Integer(1), 62, true),
+            Object::Integer(1 << 62)
+        );
+        assert_eq!(
+            shift(&Object::Integer(1), 63, true),
+            big("9223372036854775808")
+        );
+        assert_eq!(
+            shift(&Object::Integer(-1), 63, true),
+            Object::Integer(i64::MIN)
+        );
+        assert_eq!(
+            shift(&Object::Integer(-1), 64, true),
+            big("-18446744073709551616")
+        );
+        assert_eq!(shift(&Object::Integer(-8), 100, false), Object::Integer(-1));
+        assert_eq!(
+            shift(&big("18446744073709551616"), 64, false),
+            Object::Integer(1)
+        );
+        let shifted = evaluate(
+            Object::Integer(1),
+            TokenType::ShiftLeft,
+            "<<",
+            Object::Integer(MAX_SHIFT),
+        );
+        assert!(matches!(shifted, Object::BigInt(_)));
+        assert_eq!(
+            fails(
+                Object::Integer(1),
+                TokenType::ShiftLeft,
+                "<<",
+                Object::Integer(MAX_SHIFT + 1)
+            ),
+            format!("Shift amount '{}' is too large", MAX_SHIFT + 1)
+        );
+        assert_eq!(
+            fails(
+                Object::Integer(1),
+                TokenType::ShiftRight,
+                ">>",
+                Object::Integer(-1)
+            ),
+            "Can not shift by a negative amount, found '-1'"
+        );
+    }
+
+    #[test]
+    fn raises_to_powers_up_to_max_exponent() {
+        let power =
+            |base: Object, exponent: Object| evaluate(base, TokenType::Power, "**", exponent);
+        assert_eq!(
+            power(Object::Integer(2), Object::Integer(62)),
+            Object::Integer(1 << 62)
+        );
+        assert_eq!(
+            power(Object::Integer(2), Object::Integer(63)),
+            big("9223372036854775808")
+        );
+        assert_eq!(
+            power(Object::Integer(-2), Object::Integer(63)),
+            Object::Integer(i64::MIN)
+        );
+        assert_eq!(
+            power(Object::Integer(2), Object::Integer(-1)),
+            Object::Float(0.5)
+        );
+        assert!(matches!(
+            power(Object::Integer(2), Object::Integer(MAX_EXPONENT)),
+            Object::BigInt(_)
+        ));
+        assert_eq!(
+            fails(
+                Object::Integer(2),
+                TokenType::Power,
+                "**",
+                Object::Integer(MAX_EXPONENT + 1)
+            ),
+            format!("Exponent '{}' is too large", MAX_EXPONENT + 1)
+        );
+        let huge = Object::Integer(1 << 32);
+        assert_eq!(power(Object::Integer(0), huge.clone()), Object::Integer(0));
+        assert_eq!(power(Object::Integer(1), huge.clone()), Object::Integer(1));
+        assert_eq!(power(Object::Integer(-1), huge), Object::Integer(1));
+        assert_eq!(
+            power(Object::Integer(-1), Object::Integer((1 << 32) + 1)),
+            Object::Integer(-1)
+        );
     }
 }
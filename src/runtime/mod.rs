@@ -1,2 +1,6 @@
+pub mod builtins;
+pub mod callable;
 pub mod environment;
+pub mod instance;
 pub mod interpreter;
+pub mod module;
@@ -0,0 +1,42 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display, Formatter, Result},
+    ptr,
+};
+
+use crate::general::object::Object;
+
+// A loaded '.dvr' file. Only the items it declared with 'pub' are visible
+// from the outside.
+pub struct Module {
+    pub name: String,
+    exports: HashMap<String, Object>,
+}
+
+impl Module {
+    pub fn new(name: String, exports: HashMap<String, Object>) -> Self {
+        Self { name, exports }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Object> {
+        self.exports.get(name).cloned()
+    }
+}
+
+impl PartialEq for Module {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+    }
+}
+
+impl Debug for Module {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "<module {}>", self.name)
+    }
+}
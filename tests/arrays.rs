@@ -0,0 +1,71 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn reads_and_writes_elements() {
+    let output = run(r#"
+        let xs = [1, 2, 3];
+        xs[1] = 20;
+        print xs[0], " ", xs[2], " ", [[1, 2], [3]][0][1];
+        print xs, " ", len(xs);
+        push(xs, 4);
+        print pop(xs), " ", xs;
+        print ["a", 1];
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "1 3 2\n[1, 20, 3] 3\n4 [1, 20, 3]\n[\"a\", 1]\n"
+    );
+}
+
+#[test]
+fn shares_arrays_between_names() {
+    let output = run("
+        let xs = [1, 2];
+        let ys = xs;
+        ys[0] = 0;
+        print xs;
+    ");
+    assert_eq!(output.unwrap(), "[0, 2]\n");
+}
+
+#[test]
+fn compares_arrays_by_value() {
+    let output = run("print [] == [], [1, [2]] == [1, [2]], [1] == [2];");
+    assert_eq!(output.unwrap(), "truetruefalse\n");
+}
+
+#[test]
+fn rejects_bad_indices() {
+    assert_eq!(
+        fails("let xs = [1, 2, 3];\nxs[10];"),
+        "[line 2] RuntimeError: Index '10' is out of range for length 3."
+    );
+    assert_eq!(
+        fails("[1][-1];"),
+        "[line 1] RuntimeError: Negative index '-1' is not allowed."
+    );
+    assert_eq!(
+        fails(r#"[1]["x"];"#),
+        "[line 1] RuntimeError: Expected an integer index, found 'x'."
+    );
+    assert_eq!(
+        fails("let a = 5;\na[0] = 1;"),
+        "[line 2] RuntimeError: Can not assign to an index of '5'."
+    );
+}
+
+#[test]
+fn compares_arrays_that_contain_themselves() {
+    let output = run("
+        let a = [1]; push(a, a);
+        let b = [1]; push(b, b);
+        let c = [2]; push(c, c);
+        print a == a;
+        print a == b;
+        print a == c;
+        print a;
+    ");
+    assert_eq!(output.unwrap(), "true\ntrue\nfalse\n[1, [...]]\n");
+}
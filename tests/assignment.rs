@@ -0,0 +1,62 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn applies_compound_operators() {
+    let output = run(r#"
+        let mut t = 10;
+        t += 5; t -= 3; t *= 2; t %= 7;
+        print t;
+        t <<= 4; t |= 1; t ^= 3; t &= 0xff; t >>= 1;
+        print t;
+        t /= 2;
+        print t;
+        let mut s = "x";
+        s += "y";
+        print s;
+    "#);
+    assert_eq!(output.unwrap(), "3\n25\n12.5\nxy\n");
+}
+
+#[test]
+fn assigns_to_fields_and_indexes() {
+    let output = run(r#"
+        struct P { x }
+        let p = P { x: 1 };
+        let xs = [1, 2];
+        let m = {"a": [1]};
+        p.x *= 10;
+        xs[1] += 40;
+        m["a"][0] -= 5;
+        print p.x, " ", xs, " ", m;
+    "#);
+    assert_eq!(output.unwrap(), "10 [1, 42] {\"a\": [-4]}\n");
+}
+
+#[test]
+fn gives_the_assigned_value() {
+    let output = run("
+        let mut a = 0;
+        let mut b = 0;
+        a = b = 7;
+        print a + b;
+        print (a += 1);
+        let mut i = 0;
+        while (i += 1) < 3 {}
+        print i;
+    ");
+    assert_eq!(output.unwrap(), "14\n8\n3\n");
+}
+
+#[test]
+fn rejects_invalid_targets() {
+    assert_eq!(
+        fails("1 = 2;"),
+        "[line 1] ParsingError: Invalid assignment target."
+    );
+    assert_eq!(
+        fails("let mut a = 1;\na + 1 = 2;"),
+        "[line 2] ParsingError: Invalid assignment target."
+    );
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn reassigns_only_mutable_variables() {
+    assert_eq!(run("let mut x = 1;\nx = 2;\nprint x;").unwrap(), "2\n");
+    assert_eq!(
+        fails("let x = 1;\nx = 2;"),
+        "[line 2] ParsingError: Can not assign twice to immutable variable 'x' declared on line 1, declare it with 'let mut x' to allow it."
+    );
+    assert_eq!(
+        fails("for i in 0..2 { i = 5; }"),
+        "[line 1] ParsingError: Can not assign to loop variable 'i' declared on line 1."
+    );
+    assert_eq!(
+        fails("fn f() {}\nf = 1;"),
+        "[line 2] ParsingError: Can not assign to function 'f' declared on line 1."
+    );
+}
+
+#[test]
+fn allows_shadowing_and_changing_contents() {
+    let output = run(r#"
+        let x = 1;
+        let x = x + 1;
+        let xs = [1];
+        xs[0] = 5;
+        fn f(a) { a = 2; a }
+        print x, " ", xs, " ", f(1);
+    "#);
+    assert_eq!(output.unwrap(), "2 [5] 2\n");
+}
+
+#[test]
+fn declares_constants() {
+    let output = run("
+        const A = 2;
+        const B = A * 3;
+        fn g() { B }
+        print g();
+    ");
+    assert_eq!(output.unwrap(), "6\n");
+    assert_eq!(
+        fails("const C = 1;\nC = 2;"),
+        "[line 2] ParsingError: Can not assign to constant 'C' declared on line 1."
+    );
+    assert_eq!(
+        fails("const X;"),
+        "[line 1] ParsingError: Expected '=' after constant name, found ';'."
+    );
+}
+
+#[test]
+fn requires_a_value_for_immutable_variables() {
+    assert_eq!(
+        fails("let x;"),
+        "[line 1] ParsingError: Expected '=' after immutable variable 'x', found ';', declare it with 'let mut x;' to assign it later."
+    );
+    assert_eq!(
+        run("let mut x; print x; x = 1; print x;").unwrap(),
+        "nil\n1\n"
+    );
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn applies_bitwise_operators_in_twos_complement() {
+    let output = run(r#"
+        let flags = 0b1010;
+        print flags & 0b0110, " ", flags | 0b0101, " ", flags ^ 0b1111, " ", ~flags, " ", ~(-1);
+        print -12 & 10, " ", -12 | 10, " ", -12 ^ 10, " ", -16 >> 2;
+        print (1 << 100) & ((1 << 100) - 1), " ", (1 << 100) >> 99, " ", ~(1 << 64);
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "2 15 5 -11 0\n0 -2 -2 -4\n0 2 -18446744073709551617\n"
+    );
+}
+
+#[test]
+fn binds_shifts_tighter_than_bitwise_and_comparisons() {
+    assert_eq!(run("print 10 & 1 << 3 == 8;").unwrap(), "true\n");
+}
+
+#[test]
+fn only_takes_integers() {
+    assert_eq!(
+        fails("print 1.5 & 1;"),
+        "[line 1] RuntimeError: Expected 'integer & integer', found '1.5 & 1'."
+    );
+    assert_eq!(
+        fails("print ~1.5;"),
+        "[line 1] RuntimeError: Expected integer after '~', found '1.5'."
+    );
+    assert_eq!(
+        fails("print 1 << 1.0;"),
+        "[line 1] RuntimeError: Expected 'integer << integer', found '1 << 1.0'."
+    );
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn gives_the_value_of_the_tail_expression() {
+    let output = run(r#"
+        let v = { let t = 4; t * 2 };
+        let w = { let t = 4; t * 2; };
+        fn f(a) { if a { "yes" } else { "no" } }
+        print v, " ", w, " ", f(true), " ", f(false);
+    "#);
+    assert_eq!(output.unwrap(), "8 nil yes no\n");
+}
+
+#[test]
+fn gives_the_value_of_the_taken_branch() {
+    let output = run(r#"
+        let x = if true { 1 } else { 2 };
+        let y = if false { 1 } else if true { "b" } else { "c" };
+        let z = if false { 1 };
+        print x, " ", y, " ", z;
+    "#);
+    assert_eq!(output.unwrap(), "1 b nil\n");
+}
+
+#[test]
+fn breaks_out_of_loops_with_a_value() {
+    let output = run(r#"
+        let mut n = 0;
+        let r = loop { n += 1; if n == 3 { break n * 10; } };
+        let u = loop { break; };
+        print r, " ", u, " ", { 'l: loop { break 'l 5; } };
+    "#);
+    assert_eq!(output.unwrap(), "30 nil 5\n");
+}
+
+#[test]
+fn only_breaks_with_a_value_from_loop() {
+    assert_eq!(
+        fails("while true { break 1; }"),
+        "[line 1] ParsingError: Can not break with a value from a 'while' or 'for' loop."
+    );
+}
+
+#[test]
+fn tells_blocks_from_maps() {
+    let output = run("
+        print { let z = 2; z * 3 };
+        print { (1, 2) };
+        print { if true { 1 } else { 2 } };
+    ");
+    assert_eq!(output.unwrap(), "6\n(1, 2)\n1\n");
+}
+
+#[test]
+fn parses_deeply_nested_blocks_quickly() {
+    let source = format!("print {}1{};", "{(".repeat(40), ")}".repeat(40));
+    assert_eq!(run(&source).unwrap(), "1\n");
+}
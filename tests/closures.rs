@@ -0,0 +1,48 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn calls_anonymous_functions() {
+    let output = run("
+        let inc = |x| x + 1;
+        let seven = || 7;
+        print inc(1);
+        print seven();
+        print (|a, b| a * b)(3, 4);
+        print inc;
+    ");
+    assert_eq!(output.unwrap(), "2\n7\n12\n<closure>\n");
+}
+
+#[test]
+fn captures_the_enclosing_scope() {
+    let output = run("
+        fn counter() { let mut n = 0; || { n += 1; n } }
+        let a = counter();
+        let b = counter();
+        a();
+        print a();
+        print b();
+    ");
+    assert_eq!(output.unwrap(), "2\n1\n");
+}
+
+#[test]
+fn passes_functions_as_values() {
+    let output = run("
+        fn twice(f, x) { f(f(x)) }
+        fn half(x) { x ~/ 2 }
+        print twice(|x| x * 2, 5);
+        print twice(half, 20);
+    ");
+    assert_eq!(output.unwrap(), "20\n5\n");
+}
+
+#[test]
+fn checks_the_number_of_arguments() {
+    assert_eq!(
+        fails("(|x| x)(1, 2);"),
+        "[line 1] RuntimeError: Closure expected 1 argument(s), found 2."
+    );
+}
@@ -0,0 +1,55 @@
+use std::{
+    env::temp_dir,
+    fs::{create_dir_all, remove_dir_all, write},
+    process::{self, Command},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static SCRIPTS: AtomicUsize = AtomicUsize::new(0);
+
+// Runs `source` as a .dvr file and gives what it printed, or what it
+// reported on stderr if it failed.
+pub fn run(source: &str) -> Result<String, String> {
+    run_files(&[("main.dvr", source)])
+}
+
+// Writes `files` as (path, source) pairs into a fresh directory and runs
+// the first one, so that it can import the others.
+pub fn run_files(files: &[(&str, &str)]) -> Result<String, String> {
+    let directory = temp_dir().join(format!(
+        "divertido-{}-{}",
+        process::id(),
+        SCRIPTS.fetch_add(1, Ordering::Relaxed)
+    ));
+    for (path, source) in files {
+        let path = directory.join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(path, source).unwrap();
+    }
+    let output = Command::new(env!("CARGO_BIN_EXE_divertido"))
+        .arg(directory.join(files[0].0))
+        .output()
+        .unwrap();
+    remove_dir_all(&directory).unwrap();
+    if output.status.success() {
+        Ok(String::from_utf8(output.stdout).unwrap())
+    } else {
+        Err(String::from_utf8(output.stderr).unwrap())
+    }
+}
+
+// Like `run`, but expects the script to fail and gives its error without
+// the file name, e.g. `[line 1] RuntimeError: ...`.
+#[allow(dead_code)]
+pub fn fails(source: &str) -> String {
+    match run(source) {
+        Ok(output) => panic!("Expected an error, printed {:?}", output),
+        Err(error) => {
+            let error = error.trim_end();
+            match (error.find('['), error.find(", line")) {
+                (Some(0), Some(comma)) => format!("[{}", &error[comma + 2..]),
+                _ => error.to_string(),
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn constructs_and_compares_variants() {
+    let output = run(r#"
+        enum Shape { Circle(r), Rect(w, h), Nothing }
+        let c = Shape::Circle(2);
+        print c, " ", Shape::Rect(1, 2), " ", Shape::Nothing;
+        print c == Shape::Circle(2), c == Shape::Circle(3), c == Shape::Nothing;
+        print Shape::Circle, " ", Shape;
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "Shape::Circle(2) Shape::Rect(1, 2) Shape::Nothing\ntruefalsefalse\n<variant Shape::Circle> <enum Shape>\n"
+    );
+}
+
+#[test]
+fn attaches_methods_to_enums() {
+    let output = run("
+        enum Light { On, Off }
+        impl Light {
+            fn toggle(self) { if self == Light::On { Light::Off } else { Light::On } }
+        }
+        print Light::On.toggle(), Light::Off.toggle();
+    ");
+    assert_eq!(output.unwrap(), "Light::OffLight::On\n");
+}
+
+#[test]
+fn rejects_bad_variants() {
+    assert_eq!(
+        fails("enum E { A(x) }\nE::A(1, 2);"),
+        "[line 2] RuntimeError: Variant 'E::A' expected 1 argument(s), found 2."
+    );
+    assert_eq!(
+        fails("enum E { A }\nE::B;"),
+        "[line 2] RuntimeError: No associated item named 'B' in 'E'."
+    );
+    assert_eq!(
+        fails("enum E { A, A }"),
+        "[line 1] ParsingError: Variant 'A' is already declared in enum 'E'."
+    );
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn catches_runtime_errors() {
+    let output = run(r#"
+        print try { 10 / nil } catch e { 0 };
+        print try { 5 } catch e { 0 };
+        let e = try {
+            [1][7]
+        } catch error { error };
+        print e.kind, " ", e.line, " ", e.message;
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "0\n5\nRuntimeError 5 Index '7' is out of range for length 1\n"
+    );
+}
+
+#[test]
+fn raises_errors_with_panic() {
+    let output = run(r#"
+        print try { panic("boom") } catch e { e.kind + ": " + e.message };
+        print try { try { panic("in") } catch e { panic("out") } } catch e { e.message };
+    "#);
+    assert_eq!(output.unwrap(), "Panic: boom\nout\n");
+    assert_eq!(fails(r#"panic("bad");"#), "[line 1] Panic: bad.");
+}
+
+#[test]
+fn lets_control_flow_pass_through() {
+    let output = run("
+        fn f() { try { return 1; } catch e { 2 } }
+        print f();
+        for i in 0..3 { try { if i == 1 { break; } print i; } catch e {} }
+    ");
+    assert_eq!(output.unwrap(), "1\n0\n");
+}
+
+#[test]
+fn rejects_unknown_error_fields() {
+    assert_eq!(
+        fails("let e = try { panic('x') } catch e { e };\ne.nope;"),
+        "[line 2] RuntimeError: Errors only have 'message', 'kind', 'line' and 'file', found 'nope'."
+    );
+}
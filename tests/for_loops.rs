@@ -0,0 +1,41 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn iterates_over_ranges() {
+    let output = run(r#"
+        let mut out = "";
+        for i in 0..3 { out += str(i); }
+        for i in 1..=3 { out += str(i); }
+        for i in 3..1 { out += "never"; }
+        print out;
+    "#);
+    assert_eq!(output.unwrap(), "012123\n");
+}
+
+#[test]
+fn iterates_over_strings_and_collections() {
+    let output = run(r#"
+        for c in "hé!" { print c; }
+        for x in [1, "a"] { print x; }
+        for key in {"a": 1, "b": 2} { print key; }
+    "#);
+    assert_eq!(output.unwrap(), "h\né\n!\n1\na\na\nb\n");
+}
+
+#[test]
+fn scopes_the_loop_variable_to_the_body() {
+    assert_eq!(
+        fails("for i in 0..2 {}\nprint i;"),
+        "[line 2] RuntimeError: Variable with name 'i' not found."
+    );
+}
+
+#[test]
+fn rejects_values_that_can_not_be_iterated() {
+    assert_eq!(
+        fails("for i in 5 {}"),
+        "[line 1] RuntimeError: Can not iterate over '5'."
+    );
+}
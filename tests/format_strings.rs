@@ -0,0 +1,71 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn interpolates_expressions() {
+    let output = run(r#"
+        let name = "ab";
+        let n = 1;
+        print f"{name} {n + 2} {[1, "x"]:?} {name:?} {{}}";
+        print f'{name}';
+        print f"{n}
+{n + 1}";
+    "#);
+    assert_eq!(output.unwrap(), "ab 3 [1, \"x\"] \"ab\" {}\nab\n1\n2\n");
+}
+
+#[test]
+fn applies_format_specs() {
+    let output = run(r#"
+        let name = "ab";
+        print f"|{name:>5}|{name:<5}|{name:^5}|{name:*^6}|{"abc":.2}|";
+        print f"|{3.14159:.2}|{-7:05}|{5:+}|{-5:+}|";
+        print f"{255:x} {255:#X} {5:#b} {8:o} {-255:#x} {1234.5:e}";
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "|   ab|ab   | ab  |**ab**|ab|\n|3.14|-0007|+5|-5|\nff 0xFF 0b101 10 -0xff 1.2345e3\n"
+    );
+}
+
+#[test]
+fn rejects_malformed_format_strings() {
+    assert_eq!(
+        fails(r#"print f"{1:q}";"#),
+        "[line 1] ParsingError: Invalid format spec ':q'."
+    );
+    assert_eq!(
+        fails(r#"print f"}";"#),
+        "[line 1] LexingError: Unmatched '}' in format string, write '}}' for a literal brace."
+    );
+    assert_eq!(
+        fails(r#"print f"{"a":x}";"#),
+        "[line 1] RuntimeError: Can not format 'a' with ':x', expected an integer."
+    );
+}
+
+#[test]
+fn ignores_precision_for_integers() {
+    let output = run(r#"
+        print f"{10 ** 30:.2}";
+        print f"{5:.2}|{-5:>6.1}|{1.5:.3}|";
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "1000000000000000000000000000000\n5|    -5|1.500|\n"
+    );
+}
+
+#[test]
+fn rejects_widths_and_precisions_above_u16_max() {
+    assert_eq!(
+        fails(r#"print f"{1.5:.70000}";"#),
+        "[line 1] ParsingError: Invalid format spec ':.70000'."
+    );
+    assert_eq!(
+        fails(r#"print f"{1.5:70000}";"#),
+        "[line 1] ParsingError: Invalid format spec ':70000'."
+    );
+    assert_eq!(run(r#"print len(f"{1:65535}");"#).unwrap(), "65535\n");
+}
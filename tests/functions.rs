@@ -0,0 +1,58 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn calls_functions_and_returns_values() {
+    let output = run(r#"
+        fn add(x, y) { return x + y; }
+        fn sign(n) { if n > 0 { return "pos"; } "neg" }
+        fn nothing() { return; }
+        fn greet() { print "hi"; }
+        print add(2, 3);
+        print sign(1), " ", sign(-1);
+        print nothing();
+        print greet();
+    "#);
+    assert_eq!(output.unwrap(), "5\npos neg\nnil\nhi\nnil\n");
+}
+
+#[test]
+fn rejects_bad_calls_and_returns() {
+    assert_eq!(
+        fails("fn f(a, b) { a + b }\nf(1);"),
+        "[line 2] RuntimeError: Function 'f' expected 2 argument(s), found 1."
+    );
+    assert_eq!(
+        fails("let x = 1;\nx(2);"),
+        "[line 2] RuntimeError: Can only call functions, found '1'."
+    );
+    assert_eq!(
+        fails("return 1;"),
+        "[line 1] ParsingError: Can not use 'return' outside of a function."
+    );
+}
+
+#[test]
+fn recurses_deeply() {
+    let output = run("
+        fn depth(n) { if n == 0 { return 0; } 1 + depth(n - 1) }
+        print depth(5000);
+    ");
+    assert_eq!(output.unwrap(), "5000\n");
+}
+
+#[test]
+fn limits_the_call_depth() {
+    let source = "
+fn forever(n) { forever(n + 1) }
+print try { forever(0) } catch error { error };
+forever(0);
+";
+    assert_eq!(
+        fails(source),
+        "[line 2] RuntimeError: Maximum call depth of 10000 exceeded."
+    );
+    let caught = run(&source.replace("forever(0);\n", "")).unwrap();
+    assert!(caught.ends_with(", line 2] RuntimeError: Maximum call depth of 10000 exceeded.\n"));
+}
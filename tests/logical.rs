@@ -0,0 +1,23 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn returns_the_deciding_operand() {
+    let output = run(r#"
+        print 1 && 2, " ", nil && 2, " ", 0 || "x", " ", false || nil;
+        let name = nil || "default";
+        print name;
+    "#);
+    assert_eq!(output.unwrap(), "2 nil 0 nil\ndefault\n");
+}
+
+#[test]
+fn short_circuits() {
+    let output = run(r#"
+        fn boom() { print "called"; true }
+        print "a" || boom(), " ", false && boom();
+        print nil || boom();
+    "#);
+    assert_eq!(output.unwrap(), "a false\ncalled\ntrue\n");
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn breaks_and_continues_the_innermost_loop() {
+    let output = run(r#"
+        let mut out = "";
+        for i in 0..10 {
+            if i % 2 == 0 { continue; }
+            if i > 7 { break; }
+            out += str(i);
+        }
+        print out;
+    "#);
+    assert_eq!(output.unwrap(), "1357\n");
+}
+
+#[test]
+fn breaks_and_continues_labeled_loops() {
+    let output = run("
+        let mut hits = 0;
+        'outer: for i in 0..5 {
+            for j in 0..5 {
+                if j > i { continue 'outer; }
+                if i == 4 { break 'outer; }
+                hits += 1;
+            }
+        }
+        print hits;
+        let mut n = 0;
+        'a: while true { while true { n += 1; if n == 3 { break 'a; } } }
+        print n;
+    ");
+    assert_eq!(output.unwrap(), "10\n3\n");
+}
+
+#[test]
+fn rejects_break_and_continue_outside_of_loops() {
+    assert_eq!(
+        fails("break;"),
+        "[line 1] ParsingError: Can not use 'break' outside of a loop."
+    );
+    assert_eq!(
+        fails("while true { fn f() { continue; } }"),
+        "[line 1] ParsingError: Can not use 'continue' outside of a loop."
+    );
+    assert_eq!(
+        fails("while true { break 'x; }"),
+        "[line 1] ParsingError: Use of undeclared label 'x'."
+    );
+}
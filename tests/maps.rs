@@ -0,0 +1,81 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn keeps_entries_in_insertion_order() {
+    let output = run(r#"
+        let m = {"b": 1, "a": 2, 3: "three", true: nil};
+        print m["a"], " ", m[3];
+        m["c"] = 4;
+        m["b"] = 10;
+        print m, " ", len(m);
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "2 three\n{\"b\": 10, \"a\": 2, 3: \"three\", true: nil, \"c\": 4} 5\n"
+    );
+}
+
+#[test]
+fn uses_map_builtins() {
+    let output = run(r#"
+        let counts = {};
+        for c in "abca" {
+            if contains_key(counts, c) { counts[c] += 1; } else { counts[c] = 1; }
+        }
+        print counts;
+        print remove(counts, "b"), " ", keys(counts), " ", values(counts);
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "{\"a\": 2, \"b\": 1, \"c\": 1}\n1 [\"a\", \"c\"] [2, 1]\n"
+    );
+}
+
+#[test]
+fn compares_maps_and_keys_by_value() {
+    let output = run(r#"print {1: 2} == {1: 2}, {1: 2} == {1: 3}, {1.0: "x"} == {1: "x"};"#);
+    assert_eq!(output.unwrap(), "truefalsetrue\n");
+}
+
+#[test]
+fn rejects_missing_and_unhashable_keys() {
+    assert_eq!(
+        fails("let m = {};\nm[\"zzz\"];"),
+        "[line 2] RuntimeError: Key '\"zzz\"' not found in map."
+    );
+    assert_eq!(
+        fails("let m = {[1]: 2};"),
+        "[line 1] RuntimeError: Expected string, number or boolean as map key, found '[1]'."
+    );
+}
+
+#[test]
+fn compares_maps_that_contain_themselves() {
+    let output = run(r#"
+        let a = {}; a["x"] = a;
+        let b = {}; b["x"] = b;
+        let c = {}; c["y"] = c;
+        print a == b;
+        print a == c;
+        print a;
+    "#);
+    assert_eq!(output.unwrap(), "true\nfalse\n{\"x\": {...}}\n");
+}
+
+#[test]
+fn takes_any_expression_as_the_first_key() {
+    let output = run(r#"
+        let k = "a";
+        let t = (5, 6);
+        print {-1: "neg"};
+        print {k + "b": 1};
+        print {t.0: 1, [2][0]: 2};
+        print {};
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "{-1: \"neg\"}\n{\"ab\": 1}\n{5: 1, 2: 2}\n{}\n"
+    );
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn matches_literals_ranges_and_alternatives() {
+    let output = run(r#"
+        for n in [0, 5, 10, 42] {
+            print match n {
+                0 => "zero",
+                1..=9 => "digit",
+                10 | 11 => "ten or eleven",
+                _ => "big",
+            };
+        }
+        print match "hi" { "hi" => 1, _ => 2 };
+    "#);
+    assert_eq!(output.unwrap(), "zero\ndigit\nten or eleven\nbig\n1\n");
+}
+
+#[test]
+fn destructures_and_binds_values() {
+    let output = run(r#"
+        enum Shape { Circle(r), Rect(w, h) }
+        fn describe(shape) {
+            match shape {
+                Shape::Circle(r) => f"circle {r}",
+                Shape::Rect(w, h) if w == h => "square",
+                Shape::Rect(w, h) => "rectangle",
+            }
+        }
+        print describe(Shape::Circle(2)), " ", describe(Shape::Rect(3, 3)), " ", describe(Shape::Rect(3, 4));
+        print match (1, [2, 3]) { (1, [a, b]) => a + b, _ => 0 };
+        print match [1, 2] { [x] => x, [x, y] => x * y, _ => 0 };
+    "#);
+    assert_eq!(output.unwrap(), "circle 2 square rectangle\n5\n2\n");
+}
+
+#[test]
+fn scopes_bindings_to_their_arm() {
+    assert_eq!(
+        fails("match 1 { x => 1 }\nprint x;"),
+        "[line 2] RuntimeError: Variable with name 'x' not found."
+    );
+}
+
+#[test]
+fn warns_about_and_fails_on_unmatched_values() {
+    let error = run("match 5 { 1 => 1 };").unwrap_err();
+    assert!(error.contains("line 1] Warning: Match is not exhaustive, consider adding a '_' arm."));
+    assert!(error.contains("line 1] RuntimeError: No match arm matched '5'."));
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn calls_methods_and_associated_functions() {
+    let output = run("
+        struct Counter { n }
+        impl Counter {
+            fn new() { Counter { n: 0 } }
+            fn inc(self) { self.n += 1; self }
+            fn get(self) { self.n }
+        }
+        let c = Counter::new();
+        c.inc().inc();
+        print c.get();
+    ");
+    assert_eq!(output.unwrap(), "2\n");
+}
+
+#[test]
+fn binds_methods_to_their_receiver() {
+    let output = run("
+        struct Counter { n }
+        impl Counter { fn get(self) { self.n } }
+        let get = Counter { n: 7 }.get;
+        print get();
+        print get;
+    ");
+    assert_eq!(output.unwrap(), "7\n<method get>\n");
+}
+
+#[test]
+fn rejects_mixing_up_methods_and_associated_functions() {
+    assert_eq!(
+        fails("struct C {}\nimpl C { fn a() {} }\nC {}.a();"),
+        "[line 3] RuntimeError: 'a' is an associated function, call it as 'C::a'."
+    );
+    assert_eq!(
+        fails("struct C {}\nC::nope();"),
+        "[line 2] RuntimeError: No associated item named 'nope' in 'C'."
+    );
+    assert_eq!(
+        fails("struct C {}\nimpl C { fn m(self) {} }\nC {}.m(1);"),
+        "[line 3] RuntimeError: Method 'm' expected 0 argument(s), found 1."
+    );
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use common::run_files;
+
+const UTIL: &str = r#"
+pub fn double(x) { x * 2 }
+pub const N = 3;
+fn hidden() { 1 }
+print "util loaded";
+"#;
+
+#[test]
+fn imports_public_items_once() {
+    let output = run_files(&[
+        (
+            "main.dvr",
+            r#"
+            use "lib/util.dvr";
+            use lib::util::{double, N};
+            print util::double(N), " ", double(1);
+            "#,
+        ),
+        ("lib/util.dvr", UTIL),
+    ]);
+    assert_eq!(output.unwrap(), "util loaded\n6 2\n");
+}
+
+#[test]
+fn loads_modules_next_to_the_importing_file() {
+    let output = run_files(&[
+        ("main.dvr", "mod util;\nprint util::N;"),
+        ("util.dvr", UTIL),
+    ]);
+    assert_eq!(output.unwrap(), "util loaded\n3\n");
+}
+
+#[test]
+fn hides_private_items() {
+    let error = run_files(&[
+        ("main.dvr", "use \"util.dvr\";\nutil::hidden();"),
+        ("util.dvr", UTIL),
+    ])
+    .unwrap_err();
+    assert!(error.contains("line 2] RuntimeError: Module '"));
+    assert!(error.ends_with("util.dvr' has no public item named 'hidden'.\n"));
+}
+
+#[test]
+fn reports_missing_modules_and_cycles() {
+    let error = run_files(&[("main.dvr", "mod nope;")]).unwrap_err();
+    assert!(error.contains("line 1] RuntimeError: Can not find module '"));
+    let error = run_files(&[
+        ("main.dvr", "use \"a.dvr\";"),
+        ("a.dvr", "use \"b.dvr\";"),
+        ("b.dvr", "use \"a.dvr\";"),
+    ])
+    .unwrap_err();
+    assert!(error.contains("b.dvr, line 1] RuntimeError: Import cycle between modules: "));
+}
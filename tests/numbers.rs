@@ -0,0 +1,43 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn keeps_integers_and_floats_apart() {
+    let output = run(r#"
+        print 1 + 2, " ", 1 + 2.0, " ", 7 % 2, " ", 7.5 % 2;
+        print 7 / 2, " ", 6 / 3, " ", 1.0, " ", -0.0, " ", 0.1 + 0.2;
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "3 3.0 1 1.5\n3.5 2.0 1.0 -0.0 0.30000000000000004\n"
+    );
+}
+
+#[test]
+fn compares_numbers_by_value() {
+    let output = run("print 2 == 2.0, 1 < 1.5, 3 > 2.5, 1 == 1.5;");
+    assert_eq!(output.unwrap(), "truetruetruefalse\n");
+}
+
+#[test]
+fn converts_between_numbers_and_strings() {
+    let output = run(r#"
+        print int(3.9), " ", int(-3.9), " ", float(2), " ", int("42") + 1, " ", str(1.5);
+    "#);
+    assert_eq!(output.unwrap(), "3 -3 2.0 43 1.5\n");
+}
+
+#[test]
+fn divides_floats_by_zero_but_not_integers() {
+    assert_eq!(
+        run("print 1.0 / 0, \" \", -1.0 / 0, \" \", 1.0 % 0;").unwrap(),
+        "inf -inf NaN\n"
+    );
+    assert_eq!(fails("1 / 0;"), "[line 1] RuntimeError: Division by zero.");
+    assert_eq!(fails("1 % 0;"), "[line 1] RuntimeError: Division by zero.");
+    assert_eq!(
+        fails("int(\"x\");"),
+        "[line 1] RuntimeError: Can not convert 'x' to an integer."
+    );
+}
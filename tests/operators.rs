@@ -0,0 +1,45 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn raises_to_powers() {
+    let output = run(r#"
+        print 2 ** 10, " ", 2 ** 3 ** 2, " ", -2 ** 2, " ", 2 ** -1, " ", 4 ** 0.5, " ", 2 ** 64;
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "1024 512 -4 0.5 2.0 18446744073709551616\n"
+    );
+}
+
+#[test]
+fn floor_divides() {
+    let output = run(r#"print 7 ~/ 2, " ", -7 ~/ 2, " ", 7.5 ~/ 2, " ", 7 ~/ -2;"#);
+    assert_eq!(output.unwrap(), "3 -4 3.0 -4\n");
+}
+
+#[test]
+fn checks_membership() {
+    let output = run(r#"
+        print 2 in [1, 2], " ", "ell" in "hello", " ", "a" in {"a": 1}, " ", 1 in (1, 2);
+        print 3 in 1..3, " ", 3 in 1..=3, " ", 2.5 in 1..3;
+        print 5 not in [1], " ", "z" not in "abc", " ", 1 + 2 in [3];
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "true true true true\nfalse true true\ntrue true true\n"
+    );
+}
+
+#[test]
+fn rejects_operands_of_the_wrong_type() {
+    assert_eq!(
+        fails("print 1 in 5;"),
+        "[line 1] RuntimeError: Expected 'string in string' or 'value in array/tuple/map/range', found '1 in 5'."
+    );
+    assert_eq!(
+        fails(r#"print "a" ** 2;"#),
+        "[line 1] RuntimeError: Expected 'number ** number', found 'a ** 2'."
+    );
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn shadows_variables_in_nested_blocks() {
+    let output = run("
+        let x = 1;
+        { let x = 2; { let x = 3; print x; } print x; }
+        print x;
+    ");
+    assert_eq!(output.unwrap(), "3\n2\n1\n");
+}
+
+#[test]
+fn assigns_through_to_enclosing_scopes() {
+    let output = run("
+        let mut y = 1;
+        { y = 5; let y = 9; }
+        print y;
+    ");
+    assert_eq!(output.unwrap(), "5\n");
+}
+
+#[test]
+fn resolves_variables_where_functions_are_defined() {
+    let output = run("
+        let x = 1;
+        fn show() { print x; }
+        { let x = 100; show(); }
+    ");
+    assert_eq!(output.unwrap(), "1\n");
+}
+
+#[test]
+fn forgets_variables_at_the_end_of_their_block() {
+    assert_eq!(
+        fails("{ let inner = 1; }\nprint inner;"),
+        "[line 2] RuntimeError: Variable with name 'inner' not found."
+    );
+}
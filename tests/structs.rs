@@ -0,0 +1,75 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn reads_and_writes_fields() {
+    let output = run(r#"
+        struct Point { x, y }
+        let p = Point { x: 1, y: 2 };
+        p.y = 5;
+        print p, " ", p.x + p.y;
+        let x = 3;
+        let y = 4;
+        print Point { x, y };
+    "#);
+    assert_eq!(
+        output.unwrap(),
+        "Point { x: 1, y: 5 } 6\nPoint { x: 3, y: 4 }\n"
+    );
+}
+
+#[test]
+fn shares_and_compares_instances() {
+    let output = run("
+        struct Point { x, y }
+        let p = Point { x: 1, y: 2 };
+        let q = p;
+        q.x = 9;
+        print p.x;
+        print Point { x: 1, y: 2 } == Point { x: 1, y: 2 };
+        print Point { x: 1, y: 2 } == Point { x: 1, y: 3 };
+    ");
+    assert_eq!(output.unwrap(), "9\ntrue\nfalse\n");
+}
+
+#[test]
+fn rejects_unknown_and_missing_fields() {
+    assert_eq!(
+        fails("struct P { x }\nP { y: 1 };"),
+        "[line 2] RuntimeError: Struct 'P' has no field named 'y'."
+    );
+    assert_eq!(
+        fails("struct P { x, y }\nP { x: 1 };"),
+        "[line 2] RuntimeError: Missing field 'y' in initializer of 'P'."
+    );
+    assert_eq!(
+        fails("struct P { x }\nlet p = P { x: 1 };\np.q = 1;"),
+        "[line 3] RuntimeError: Struct 'P' has no field named 'q'."
+    );
+    assert_eq!(
+        fails("struct P { x }\nlet p = P { x: 1 };\np.z;"),
+        "[line 3] RuntimeError: 'P' has no field or method named 'z'."
+    );
+    assert_eq!(
+        fails("5.x;"),
+        "[line 1] RuntimeError: Only struct instances and enum values have fields or methods, found '5'."
+    );
+}
+
+#[test]
+fn compares_instances_that_contain_themselves() {
+    let output = run("
+        struct Node { value, next }
+        let a = Node { value: 1, next: nil }; a.next = a;
+        let b = Node { value: 1, next: nil }; b.next = b;
+        let c = Node { value: 2, next: nil }; c.next = c;
+        print a == b;
+        print a == c;
+        print a;
+    ");
+    assert_eq!(
+        output.unwrap(),
+        "true\nfalse\nNode { value: 1, next: Node { ... } }\n"
+    );
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::{fails, run};
+
+#[test]
+fn builds_and_indexes_tuples() {
+    let output = run(r#"
+        let t = ((1, 2), "x");
+        print t.0.1, " ", t, " ", (), " ", (1,);
+        print (1, 2) == (1, 2), (1, 2) == (2, 1);
+    "#);
+    assert_eq!(output.unwrap(), "2 ((1, 2), \"x\") () (1,)\ntruefalse\n");
+}
+
+#[test]
+fn destructures_multiple_return_values() {
+    let output = run(r#"
+        fn divmod(a, b) { (a ~/ b, a % b) }
+        let (q, r) = divmod(17, 5);
+        let [first, rest..] = [1, 2, 3];
+        let (_, y) = (1, 2);
+        print q, " ", r, " ", first, " ", rest, " ", y;
+        let mut (a, b) = (1, 2);
+        (a, b) = (b, a);
+        print a, " ", b;
+    "#);
+    assert_eq!(output.unwrap(), "3 2 1 [2, 3] 2\n2 1\n");
+}
+
+#[test]
+fn rejects_mismatched_patterns_and_indices() {
+    assert_eq!(
+        fails("let (a, b) = (1, 2, 3);"),
+        "[line 1] RuntimeError: Can not destructure '(1, 2, 3)' with this pattern."
+    );
+    assert_eq!(
+        fails("let (a, b);"),
+        "[line 1] ParsingError: Expected '=' after destructuring pattern, found ';'."
+    );
+    assert_eq!(
+        fails("let t = (1, 2);\nt.5;"),
+        "[line 2] RuntimeError: Tuple '(1, 2)' has no element '5'."
+    );
+}